@@ -0,0 +1,191 @@
+// Queryz derive: derive macros for typed Move struct bindings
+// The generated code refers to `::queryz`, so the macros are used through the queryz crate
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Fields, GenericParam, LitStr, Type};
+
+/// Derive `MoveType` and `MoveStruct` for a Rust mirror of a Move struct
+///
+/// ```ignore
+/// #[derive(Deserialize, MoveStruct)]
+/// #[move_struct(address = "0x2", module = "balance", name = "Supply")]
+/// struct Supply<T> {
+///     value: u64,
+///     #[move_field(skip)]
+///     phantom: PhantomData<T>,
+/// }
+/// ```
+///
+/// Struct attributes:
+/// * `address` - Address of the package defining the struct (required)
+/// * `module` - Module defining the struct (required)
+/// * `name` - Name of the struct, defaults to the Rust name
+///
+/// Field attributes:
+/// * `rename = "..."` - Name of the field in Move, defaults to the Rust name
+/// * `skip` - Leave out a field that takes no BCS bytes; `PhantomData` fields are skipped automatically
+///
+/// Type params of the Rust struct become the type params of the Move struct, in order.
+#[proc_macro_derive(MoveStruct, attributes(move_struct, move_field))]
+pub fn derive_move_struct(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_move_struct(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Settings from the `#[move_struct(...)]` attribute
+struct StructAttrs {
+    address: LitStr,
+    module: LitStr,
+    name: Option<LitStr>,
+}
+
+/// A field included in the Move layout
+struct MoveField {
+    name: String,
+    ty: Type,
+}
+
+fn expand_move_struct(mut input: DeriveInput) -> syn::Result<TokenStream2> {
+    let attrs = parse_struct_attrs(&input)?;
+    let fields = parse_fields(&input)?;
+
+    let ident = &input.ident;
+    let address = &attrs.address;
+    let module = &attrs.module;
+    let name = attrs.name.clone().unwrap_or_else(|| LitStr::new(&ident.to_string(), ident.span()));
+
+    let type_params: Vec<_> = input.generics.type_params().map(|param| param.ident.clone()).collect();
+    for param in input.generics.params.iter_mut() {
+        if let GenericParam::Type(param) = param {
+            param.bounds.push(parse_quote!(::queryz::types::move_type::MoveType));
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let field_names = fields.iter().map(|field| &field.name);
+    let field_types = fields.iter().map(|field| &field.ty);
+
+    Ok(quote! {
+        impl #impl_generics ::queryz::types::move_type::MoveType for #ident #ty_generics #where_clause {
+            fn type_tag() -> ::queryz::types::move_type::TypeTag {
+                ::queryz::types::move_type::TypeTag::Struct(::std::boxed::Box::new(
+                    <Self as ::queryz::types::move_type::MoveStruct>::struct_tag(),
+                ))
+            }
+
+            fn layout() -> ::queryz::types::move_type::MoveTypeLayout {
+                ::queryz::types::move_type::MoveTypeLayout::Struct(
+                    <Self as ::queryz::types::move_type::MoveStruct>::struct_layout(),
+                )
+            }
+        }
+
+        impl #impl_generics ::queryz::types::move_type::MoveStruct for #ident #ty_generics #where_clause {
+            fn struct_tag() -> ::queryz::types::move_type::StructTag {
+                ::queryz::types::move_type::struct_tag(
+                    #address,
+                    #module,
+                    #name,
+                    ::std::vec![#(<#type_params as ::queryz::types::move_type::MoveType>::type_tag()),*],
+                )
+            }
+
+            fn struct_layout() -> ::queryz::types::move_type::MoveStructLayout {
+                ::queryz::types::move_type::MoveStructLayout {
+                    type_: <Self as ::queryz::types::move_type::MoveStruct>::struct_tag(),
+                    fields: ::std::vec![
+                        #((
+                            ::std::string::String::from(#field_names),
+                            <#field_types as ::queryz::types::move_type::MoveType>::layout(),
+                        )),*
+                    ],
+                }
+            }
+        }
+    })
+}
+
+fn parse_struct_attrs(input: &DeriveInput) -> syn::Result<StructAttrs> {
+    let mut address = None;
+    let mut module = None;
+    let mut name = None;
+
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("move_struct")) {
+        attr.parse_nested_meta(|meta| {
+            let value: LitStr = meta.value()?.parse()?;
+            if meta.path.is_ident("address") {
+                let hex = value.value();
+                let hex = hex.strip_prefix("0x").unwrap_or(&hex);
+                if hex.is_empty() || hex.len() > 64 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return Err(meta.error("address must be a hex address such as \"0x2\""));
+                }
+                address = Some(value);
+            } else if meta.path.is_ident("module") {
+                module = Some(value);
+            } else if meta.path.is_ident("name") {
+                name = Some(value);
+            } else {
+                return Err(meta.error("expected `address`, `module` or `name`"));
+            }
+            Ok(())
+        })?;
+    }
+
+    let missing = |key: &str| {
+        syn::Error::new_spanned(&input.ident, format!("missing `#[move_struct({} = \"...\")]`", key))
+    };
+    Ok(StructAttrs {
+        address: address.ok_or_else(|| missing("address"))?,
+        module: module.ok_or_else(|| missing("module"))?,
+        name,
+    })
+}
+
+fn parse_fields(input: &DeriveInput) -> syn::Result<Vec<MoveField>> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(&input.ident, "MoveStruct can only be derived for structs"));
+    };
+
+    let mut fields = vec![];
+    for (index, field) in data.fields.iter().enumerate() {
+        let mut rename = None;
+        let mut skip = is_phantom_data(&field.ty);
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("move_field")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("skip") {
+                    skip = true;
+                } else {
+                    return Err(meta.error("expected `rename` or `skip`"));
+                }
+                Ok(())
+            })?;
+        }
+        if skip {
+            continue;
+        }
+
+        // Positional Move structs name their fields pos0, pos1, ...
+        let name = match (&data.fields, &field.ident) {
+            (Fields::Named(_), Some(ident)) => ident.to_string().trim_start_matches("r#").to_string(),
+            _ => format!("pos{}", index),
+        };
+        fields.push(MoveField {
+            name: rename.unwrap_or(name),
+            ty: field.ty.clone(),
+        });
+    }
+    Ok(fields)
+}
+
+fn is_phantom_data(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path.path.segments.last().is_some_and(|segment| segment.ident == "PhantomData"),
+        _ => false,
+    }
+}
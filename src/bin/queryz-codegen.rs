@@ -0,0 +1,102 @@
+// queryz-codegen: generate Rust bindings for the structs of a Move package
+//
+// Usage:
+//   queryz-codegen --package <ID> [--rpc <URL>] [--extern <MOVE_TYPE>=<RUST_PATH>]... [--out <FILE>]
+//   queryz-codegen --path <DIR> [--address <ADDRESS>] [--extern <MOVE_TYPE>=<RUST_PATH>]... [--out <FILE>]
+
+use anyhow::{anyhow, Result};
+use move_core_types::account_address::AccountAddress;
+use queryz::codegen::Codegen;
+use queryz::SuiQueryZClient;
+use std::path::PathBuf;
+use sui_types::base_types::ObjectID;
+
+const DEFAULT_RPC_URL: &str = "https://fullnode.mainnet.sui.io:443";
+
+const USAGE: &str = "Usage:
+  queryz-codegen --package <ID> [--rpc <URL>] [--extern <MOVE_TYPE>=<RUST_PATH>]... [--out <FILE>]
+  queryz-codegen --path <DIR> [--address <ADDRESS>] [--extern <MOVE_TYPE>=<RUST_PATH>]... [--out <FILE>]";
+
+/// Where the package comes from
+enum Source {
+    Chain { package_id: ObjectID, rpc_url: String },
+    Path { path: PathBuf, address: Option<AccountAddress> },
+}
+
+struct Args {
+    source: Source,
+    extern_types: Vec<(String, String)>,
+    out: Option<PathBuf>,
+}
+
+fn parse_args() -> Result<Args> {
+    let mut package_id = None;
+    let mut rpc_url = None;
+    let mut path = None;
+    let mut address = None;
+    let mut extern_types = vec![];
+    let mut out = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| anyhow!("Missing value for {}\n{}", arg, USAGE));
+        match arg.as_str() {
+            "--package" => package_id = Some(value()?.parse::<ObjectID>()?),
+            "--rpc" => rpc_url = Some(value()?),
+            "--path" => path = Some(PathBuf::from(value()?)),
+            "--address" => address = Some(AccountAddress::from_hex_literal(&value()?)?),
+            "--extern" => {
+                let mapping = value()?;
+                let (move_type, rust_path) = mapping
+                    .split_once('=')
+                    .ok_or_else(|| anyhow!("Expected <MOVE_TYPE>=<RUST_PATH>, got: {}", mapping))?;
+                extern_types.push((move_type.to_string(), rust_path.to_string()));
+            }
+            "--out" => out = Some(PathBuf::from(value()?)),
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                std::process::exit(0);
+            }
+            _ => return Err(anyhow!("Unknown argument: {}\n{}", arg, USAGE)),
+        }
+    }
+
+    let source = match (package_id, path) {
+        (Some(package_id), None) => Source::Chain {
+            package_id,
+            rpc_url: rpc_url.unwrap_or_else(|| DEFAULT_RPC_URL.to_string()),
+        },
+        (None, Some(path)) => Source::Path { path, address },
+        _ => return Err(anyhow!("Expected exactly one of --package or --path\n{}", USAGE)),
+    };
+
+    Ok(Args {
+        source,
+        extern_types,
+        out,
+    })
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = parse_args()?;
+
+    let codegen = args
+        .extern_types
+        .iter()
+        .fold(Codegen::new(), |codegen, (move_type, rust_path)| codegen.with_extern_type(move_type, rust_path));
+
+    let source = match args.source {
+        Source::Chain { package_id, rpc_url } => {
+            let client = SuiQueryZClient::new(&rpc_url).await?;
+            codegen.generate_from_chain(&client, package_id).await?
+        }
+        Source::Path { path, address } => codegen.generate_from_path(&path, address)?,
+    };
+
+    match args.out {
+        Some(out) => std::fs::write(&out, source)?,
+        None => print!("{}", source),
+    }
+    Ok(())
+}
@@ -1,11 +1,18 @@
 use anyhow::Result;
-use sui_json_rpc_types::{Balance, DynamicFieldPage, SuiCoinMetadata, SuiObjectDataOptions, SuiObjectResponse};
+use sui_json_rpc_types::{Balance, CoinPage, DelegatedStake, DynamicFieldPage, ObjectsPage, SuiCoinMetadata, SuiMoveNormalizedModule, SuiObjectDataOptions, SuiObjectResponse, SuiObjectResponseQuery, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions, SuiTransactionBlockResponseQuery, TransactionBlocksPage};
+use sui_types::gas_coin::Supply;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use sui_sdk::{SuiClient, SuiClientBuilder};
 use sui_sdk::types::base_types::SuiAddress;
 use sui_types::base_types::ObjectID;
+use sui_types::digests::TransactionDigest;
 
 use crate::core_clients::coin_metadata::CoinMetadataClient;
+use crate::core_clients::metadata_store::CoinMetadataStore;
+use crate::core_clients::move_layout::MoveLayoutClient;
+use crate::core_clients::name_service::{NameResolver, NameServiceClient, RpcNameResolver};
+use crate::core_clients::token_registry::TokenRegistry;
 
 /// SuiClient is a wrapper around the Sui SDK client
 /// It provides simplified access to common Sui operations
@@ -13,11 +20,15 @@ use crate::core_clients::coin_metadata::CoinMetadataClient;
 pub struct SuiQueryZClient {
     sui_client: Arc<SuiClient>,
     coin_metadata_client: Arc<CoinMetadataClient>,
+    name_service_client: Arc<NameServiceClient>,
+    move_layout_client: Arc<MoveLayoutClient>,
 }
 
 impl SuiQueryZClient {
     /// Create a new SuiClient connected to the specified RPC URL
     ///
+    /// Coin metadata is read from chain; call `with_token_registry` to override it with a registry.
+    ///
     /// # Arguments
     /// * `rpc_url` - The URL of the Sui RPC endpoint
     ///
@@ -29,12 +40,90 @@ impl SuiQueryZClient {
             .await?;
 
         let coin_metadata_client = CoinMetadataClient::new(Arc::new(sui_client.clone()));
+        let name_service_client = NameServiceClient::new(Arc::new(RpcNameResolver::new(Arc::new(sui_client.clone()))));
+        let move_layout_client = MoveLayoutClient::new(Arc::new(sui_client.clone()));
         
         Ok(Self {
             sui_client: Arc::new(sui_client),
             coin_metadata_client: Arc::new(coin_metadata_client),
+            name_service_client: Arc::new(name_service_client),
+            move_layout_client: Arc::new(move_layout_client),
         })
     }
+
+    /// Persist coin metadata in a store so it survives process restarts
+    ///
+    /// The cache settings, fetcher and token registry of the current coin metadata client are kept.
+    ///
+    /// # Arguments
+    /// * `store` - The store to load metadata from and write fetched metadata to
+    ///
+    /// # Returns
+    /// * `Result<SuiQueryZClient>` - The updated client or an error if the store cannot be loaded
+    pub fn with_coin_metadata_store(mut self, store: Arc<dyn CoinMetadataStore>) -> Result<Self> {
+        let token_registry = self.coin_metadata_client.token_registry();
+        self.coin_metadata_client = Arc::new(self.rebuild_coin_metadata_client(token_registry, Some(store))?);
+        Ok(self)
+    }
+
+    /// Use a token registry whose entries take precedence over on-chain metadata
+    ///
+    /// The cache settings, fetcher and store of the current coin metadata client are kept.
+    /// Pass `TokenRegistry::bundled()` for the well-known mainnet tokens, merged with your own entries if needed.
+    ///
+    /// # Arguments
+    /// * `token_registry` - The registry consulted before on-chain metadata
+    ///
+    /// # Returns
+    /// * `Result<SuiQueryZClient>` - The updated client or an error if the store cannot be reloaded
+    pub fn with_token_registry(mut self, token_registry: TokenRegistry) -> Result<Self> {
+        let store = self.coin_metadata_client.store();
+        self.coin_metadata_client = Arc::new(self.rebuild_coin_metadata_client(Arc::new(token_registry), store)?);
+        Ok(self)
+    }
+
+    /// Use a custom coin metadata client, e.g. one with different cache settings
+    ///
+    /// This replaces the store and token registry set before; builders called afterwards keep
+    /// the settings of this client.
+    ///
+    /// # Arguments
+    /// * `coin_metadata_client` - The coin metadata client to use
+    ///
+    /// # Returns
+    /// * `SuiQueryZClient` - The updated client
+    pub fn with_coin_metadata_client(mut self, coin_metadata_client: CoinMetadataClient) -> Self {
+        self.coin_metadata_client = Arc::new(coin_metadata_client);
+        self
+    }
+
+    /// Build a coin metadata client with the fetcher and cache settings of the current one
+    fn rebuild_coin_metadata_client(
+        &self,
+        token_registry: Arc<TokenRegistry>,
+        store: Option<Arc<dyn CoinMetadataStore>>,
+    ) -> Result<CoinMetadataClient> {
+        let current = &self.coin_metadata_client;
+        let coin_metadata_client = CoinMetadataClient::from_fetcher(current.fetcher())
+            .with_cache_config(current.cache_config().clone())
+            .with_token_registry(token_registry);
+        match store {
+            Some(store) => coin_metadata_client.with_store(store),
+            None => Ok(coin_metadata_client),
+        }
+    }
+
+    /// Use a custom resolver for SuiNS names, e.g. a mock in tests
+    ///
+    /// # Arguments
+    /// * `resolver` - The resolver to use on name cache misses
+    ///
+    /// # Returns
+    /// * `SuiQueryZClient` - The updated client
+    pub fn with_name_resolver(mut self, resolver: Arc<dyn NameResolver>) -> Self {
+        self.name_service_client = Arc::new(NameServiceClient::new(resolver));
+        self
+    }
     
     /// Get the inner SuiClient
     ///
@@ -44,6 +133,22 @@ impl SuiQueryZClient {
         self.sui_client.clone()
     }
     
+    /// Get the coin metadata client
+    ///
+    /// # Returns
+    /// * `Arc<CoinMetadataClient>` - The coin metadata client
+    pub fn coin_metadata_client(&self) -> Arc<CoinMetadataClient> {
+        self.coin_metadata_client.clone()
+    }
+    
+    /// Get the Move layout client
+    ///
+    /// # Returns
+    /// * `Arc<MoveLayoutClient>` - The client building BCS layouts of Move types
+    pub fn move_layout_client(&self) -> Arc<MoveLayoutClient> {
+        self.move_layout_client.clone()
+    }
+    
     /// Get object data by ID
     ///
     /// # Arguments
@@ -55,6 +160,29 @@ impl SuiQueryZClient {
         Ok(self.sui_client.read_api().get_object_with_options(object_id, SuiObjectDataOptions::default()).await?)
     }
     
+    /// Get object data by ID with the given data options
+    ///
+    /// # Arguments
+    /// * `object_id` - The ID of the object to retrieve
+    /// * `options` - Which parts of the object to return
+    ///
+    /// # Returns
+    /// * `Result<SuiObjectResponse>` - The object data or an error
+    pub async fn get_object_with_options(&self, object_id: ObjectID, options: SuiObjectDataOptions) -> Result<SuiObjectResponse> {
+        Ok(self.sui_client.read_api().get_object_with_options(object_id, options).await?)
+    }
+
+    /// Get the normalized modules of a package
+    ///
+    /// # Arguments
+    /// * `package_id` - The ID of the package
+    ///
+    /// # Returns
+    /// * `Result<BTreeMap<String, SuiMoveNormalizedModule>>` - The modules using the module name as key or an error
+    pub async fn get_normalized_move_modules_by_package(&self, package_id: ObjectID) -> Result<BTreeMap<String, SuiMoveNormalizedModule>> {
+        Ok(self.sui_client.read_api().get_normalized_move_modules_by_package(package_id).await?)
+    }
+
     /// Get objects owned by an address
     ///
     /// # Arguments
@@ -67,6 +195,46 @@ impl SuiQueryZClient {
         Ok(objects.data)
     }
 
+    /// Get a page of objects owned by an address
+    ///
+    /// # Arguments
+    /// * `address` - The address to query
+    /// * `query` - Optional filter and data options for the returned objects
+    /// * `cursor` - The cursor returned by the previous page, if any
+    /// * `limit` - The maximum number of objects to return
+    ///
+    /// # Returns
+    /// * `Result<ObjectsPage>` - A page of objects owned by the address or an error
+    pub async fn get_owned_objects(&self, address: SuiAddress, query: Option<SuiObjectResponseQuery>, cursor: Option<ObjectID>, limit: Option<usize>) -> Result<ObjectsPage> {
+        Ok(self.sui_client.read_api().get_owned_objects(address, query, cursor, limit).await?)
+    }
+
+    /// Get a page of transactions matching a query
+    ///
+    /// # Arguments
+    /// * `query` - The transaction filter and response options
+    /// * `cursor` - The cursor returned by the previous page, if any
+    /// * `limit` - The maximum number of transactions to return
+    /// * `descending_order` - Whether to return the newest transactions first
+    ///
+    /// # Returns
+    /// * `Result<TransactionBlocksPage>` - A page of transactions or an error
+    pub async fn query_transaction_blocks(&self, query: SuiTransactionBlockResponseQuery, cursor: Option<TransactionDigest>, limit: Option<usize>, descending_order: bool) -> Result<TransactionBlocksPage> {
+        Ok(self.sui_client.read_api().query_transaction_blocks(query, cursor, limit, descending_order).await?)
+    }
+
+    /// Get a transaction by digest
+    ///
+    /// # Arguments
+    /// * `digest` - The digest of the transaction
+    /// * `options` - Which parts of the transaction to return
+    ///
+    /// # Returns
+    /// * `Result<SuiTransactionBlockResponse>` - The transaction or an error
+    pub async fn get_transaction_with_options(&self, digest: TransactionDigest, options: SuiTransactionBlockResponseOptions) -> Result<SuiTransactionBlockResponse> {
+        Ok(self.sui_client.read_api().get_transaction_with_options(digest, options).await?)
+    }
+
     pub async fn get_dynamic_fields(&self, object_id: ObjectID, cursor: Option<ObjectID>, limit: Option<usize>) -> Result<DynamicFieldPage> {
         Ok(self.sui_client.read_api().get_dynamic_fields(object_id, cursor, limit).await?)
     }
@@ -75,6 +243,28 @@ impl SuiQueryZClient {
         Ok(self.sui_client.read_api().multi_get_object_with_options(object_ids, options).await?)
     }
 
+    /// Parse an address, resolving it first if it is a SuiNS name such as "example.sui"
+    ///
+    /// # Arguments
+    /// * `address_or_name` - A hex address or a SuiNS name
+    ///
+    /// # Returns
+    /// * `Result<SuiAddress>` - The address or an error
+    pub async fn resolve_address(&self, address_or_name: &str) -> Result<SuiAddress> {
+        self.name_service_client.resolve_address(address_or_name).await
+    }
+
+    /// Get the primary SuiNS name of an address
+    ///
+    /// # Arguments
+    /// * `address` - The address to look up
+    ///
+    /// # Returns
+    /// * `Result<Option<String>>` - The primary name, `None` if the address has none, or an error
+    pub async fn get_primary_name(&self, address: SuiAddress) -> Result<Option<String>> {
+        self.name_service_client.primary_name(address).await
+    }
+
     /// Get coin metadata
     ///
     /// # Arguments
@@ -86,14 +276,90 @@ impl SuiQueryZClient {
         self.coin_metadata_client.get_metadata(coin_type).await
     }
 
+    /// Get coin metadata if the coin has any
+    ///
+    /// # Arguments
+    /// * `coin_type` - The type of the coin to get metadata for
+    ///
+    /// # Returns
+    /// * `Result<Option<CoinMetadata>>` - The metadata, `None` if the coin has no metadata, or an error
+    pub async fn try_get_coin_metadata(&self, coin_type: &str) -> Result<Option<SuiCoinMetadata>> {
+        self.coin_metadata_client.try_get_metadata(coin_type).await
+    }
+
+    /// Get the total supply of a coin type
+    ///
+    /// # Arguments
+    /// * `coin_type` - The type of the coin
+    ///
+    /// # Returns
+    /// * `Result<Supply>` - The total supply in the coin's smallest unit or an error
+    pub async fn get_total_supply(&self, coin_type: &str) -> Result<Supply> {
+        Ok(self.sui_client.coin_read_api().get_total_supply(coin_type.to_string()).await?)
+    }
+
     pub async fn get_coin_balance(&self, address: SuiAddress, coin_type: &str) -> Result<u64> {
-        let balance = self.sui_client.coin_read_api().get_all_balances(address).await?;
-        let coin_balance = balance.iter().find(|balance| balance.coin_type == coin_type).map(|balance| balance.total_balance).unwrap_or(0);
-        Ok(coin_balance as u64)
+        let balance = self.sui_client.coin_read_api().get_balance(address, Some(coin_type.to_string())).await?;
+        Ok(balance.total_balance as u64)
+    }
+
+    /// Get a page of coin objects of a type owned by an address
+    ///
+    /// # Arguments
+    /// * `address` - The address to query
+    /// * `coin_type` - The coin type to list, defaults to SUI when `None`
+    /// * `cursor` - The cursor returned by the previous page, if any
+    /// * `limit` - The maximum number of coins to return
+    ///
+    /// # Returns
+    /// * `Result<CoinPage>` - A page of coin objects or an error
+    pub async fn get_coins(&self, address: SuiAddress, coin_type: Option<String>, cursor: Option<ObjectID>, limit: Option<usize>) -> Result<CoinPage> {
+        Ok(self.sui_client.coin_read_api().get_coins(address, coin_type, cursor, limit).await?)
+    }
+
+    /// Get a page of coin objects of all types owned by an address
+    ///
+    /// # Arguments
+    /// * `address` - The address to query
+    /// * `cursor` - The cursor returned by the previous page, if any
+    /// * `limit` - The maximum number of coins to return
+    ///
+    /// # Returns
+    /// * `Result<CoinPage>` - A page of coin objects or an error
+    pub async fn get_all_coins(&self, address: SuiAddress, cursor: Option<ObjectID>, limit: Option<usize>) -> Result<CoinPage> {
+        Ok(self.sui_client.coin_read_api().get_all_coins(address, cursor, limit).await?)
     }
 
     pub async fn get_coin_balances(&self, address: SuiAddress) -> Result<Vec<Balance>> {
         let balance = self.sui_client.coin_read_api().get_all_balances(address).await?;
         Ok(balance)
     }
+
+    /// Get the current epoch
+    ///
+    /// # Returns
+    /// * `Result<u64>` - The current epoch or an error
+    pub async fn get_current_epoch(&self) -> Result<u64> {
+        let system_state = self.sui_client.governance_api().get_latest_sui_system_state().await?;
+        Ok(system_state.epoch)
+    }
+
+    /// Get the sequence number of the latest checkpoint
+    ///
+    /// # Returns
+    /// * `Result<u64>` - The latest checkpoint sequence number or an error
+    pub async fn get_latest_checkpoint_sequence_number(&self) -> Result<u64> {
+        Ok(self.sui_client.read_api().get_latest_checkpoint_sequence_number().await?)
+    }
+
+    /// Get the staked SUI of an address, grouped by validator
+    ///
+    /// # Arguments
+    /// * `address` - The address to query
+    ///
+    /// # Returns
+    /// * `Result<Vec<DelegatedStake>>` - The stakes of the address or an error
+    pub async fn get_stakes(&self, address: SuiAddress) -> Result<Vec<DelegatedStake>> {
+        Ok(self.sui_client.governance_api().get_stakes(address).await?)
+    }
 } 
@@ -0,0 +1,85 @@
+use serde::Deserialize;
+use std::marker::PhantomData;
+use sui_types::id::UID;
+
+use crate::types::move_type::MoveStruct;
+
+/// Mirror of `0x2::balance::Balance`
+#[derive(Debug, Deserialize, MoveStruct)]
+#[move_struct(address = "0x2", module = "balance", name = "Balance")]
+pub struct Balance<T> {
+    pub value: u64,
+    pub phantom: PhantomData<T>,
+}
+
+/// Mirror of `0x2::balance::Supply`
+#[derive(Debug, Deserialize, MoveStruct)]
+#[move_struct(address = "0x2", module = "balance", name = "Supply")]
+pub struct Supply<T> {
+    pub value: u64,
+    pub phantom: PhantomData<T>,
+}
+
+/// Mirror of `0x2::coin::Coin`
+#[derive(Debug, Deserialize, MoveStruct)]
+#[move_struct(address = "0x2", module = "coin", name = "Coin")]
+pub struct Coin<T> {
+    pub id: UID,
+    pub balance: Balance<T>,
+}
+
+/// Mirror of `0x2::bag::Bag`
+#[derive(Debug, Deserialize, MoveStruct)]
+#[move_struct(address = "0x2", module = "bag", name = "Bag")]
+pub struct Bag {
+    pub id: UID,
+    pub size: u64,
+}
+
+/// Mirror of `0x2::object_bag::ObjectBag`
+#[derive(Debug, Deserialize, MoveStruct)]
+#[move_struct(address = "0x2", module = "object_bag", name = "ObjectBag")]
+pub struct ObjectBag {
+    pub id: UID,
+    pub size: u64,
+}
+
+/// Mirror of `0x2::table::Table`
+#[derive(Debug, Deserialize, MoveStruct)]
+#[move_struct(address = "0x2", module = "table", name = "Table")]
+pub struct Table<K, V> {
+    pub id: UID,
+    pub size: u64,
+    pub phantom: PhantomData<(K, V)>,
+}
+
+/// Mirror of `0x2::object_table::ObjectTable`
+#[derive(Debug, Deserialize, MoveStruct)]
+#[move_struct(address = "0x2", module = "object_table", name = "ObjectTable")]
+pub struct ObjectTable<K, V> {
+    pub id: UID,
+    pub size: u64,
+    pub phantom: PhantomData<(K, V)>,
+}
+
+/// Mirror of `0x2::vec_set::VecSet`
+#[derive(Debug, Deserialize, MoveStruct)]
+#[move_struct(address = "0x2", module = "vec_set", name = "VecSet")]
+pub struct VecSet<K> {
+    pub contents: Vec<K>,
+}
+
+/// Mirror of `0x2::vec_map::VecMap`
+#[derive(Debug, Deserialize, MoveStruct)]
+#[move_struct(address = "0x2", module = "vec_map", name = "VecMap")]
+pub struct VecMap<K, V> {
+    pub contents: Vec<VecMapEntry<K, V>>,
+}
+
+/// Mirror of `0x2::vec_map::Entry`
+#[derive(Debug, Deserialize, MoveStruct)]
+#[move_struct(address = "0x2", module = "vec_map", name = "Entry")]
+pub struct VecMapEntry<K, V> {
+    pub key: K,
+    pub value: V,
+}
@@ -0,0 +1,386 @@
+// Codegen module generates Rust bindings for the structs of a Move package
+
+pub mod framework;
+pub mod model;
+pub mod prelude;
+
+use anyhow::{anyhow, Result};
+use move_core_types::account_address::AccountAddress;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+use sui_move_build::BuildConfig;
+use sui_types::base_types::ObjectID;
+
+use crate::client::SuiQueryZClient;
+use crate::utils::coin::format_coin_address;
+use model::{FieldType, ModuleModel, PackageModel, StructModel};
+
+/// Path of the prelude used by generated code
+const PRELUDE: &str = "::queryz::codegen::prelude";
+
+/// Rust types used for framework structs, keyed by the Move type without type params
+const BUILTIN_TYPES: &[(&str, &str)] = &[
+    ("0x1::string::String", "::std::string::String"),
+    ("0x1::ascii::String", "::queryz::codegen::prelude::AsciiString"),
+    ("0x1::option::Option", "::std::option::Option"),
+    ("0x1::type_name::TypeName", "::queryz::codegen::prelude::TypeName"),
+    ("0x2::object::UID", "::queryz::codegen::prelude::UID"),
+    ("0x2::object::ID", "::queryz::codegen::prelude::ID"),
+    ("0x2::balance::Balance", "::queryz::codegen::prelude::Balance"),
+    ("0x2::balance::Supply", "::queryz::codegen::prelude::Supply"),
+    ("0x2::coin::Coin", "::queryz::codegen::prelude::Coin"),
+    ("0x2::bag::Bag", "::queryz::codegen::prelude::Bag"),
+    ("0x2::object_bag::ObjectBag", "::queryz::codegen::prelude::ObjectBag"),
+    ("0x2::table::Table", "::queryz::codegen::prelude::Table"),
+    ("0x2::object_table::ObjectTable", "::queryz::codegen::prelude::ObjectTable"),
+    ("0x2::vec_set::VecSet", "::queryz::codegen::prelude::VecSet"),
+    ("0x2::vec_map::VecMap", "::queryz::codegen::prelude::VecMap"),
+    ("0x2::vec_map::Entry", "::queryz::codegen::prelude::VecMapEntry"),
+];
+
+/// Rust keywords that must be written as raw identifiers when used as field or module names
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false",
+    "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "static",
+    "struct", "trait", "true", "try", "type", "unsafe", "use", "where", "while", "yield",
+];
+
+/// Codegen generates Rust bindings for the structs of a Move package
+///
+/// Every struct becomes a Rust struct deriving `Deserialize` and `MoveStruct`, in a Rust
+/// module named after its Move module. Structs with `Table` or `Bag` fields get helpers
+/// listing the entries of those fields.
+#[derive(Debug, Clone)]
+pub struct Codegen {
+    /// Rust types for structs outside the package, keyed by the Move type without type params
+    extern_types: BTreeMap<String, String>,
+}
+
+impl Default for Codegen {
+    fn default() -> Self {
+        let extern_types = BUILTIN_TYPES
+            .iter()
+            .map(|(move_type, rust_path)| (format_coin_address(move_type), rust_path.to_string()))
+            .collect();
+        Self { extern_types }
+    }
+}
+
+impl Codegen {
+    /// Create a new Codegen knowing the Move standard library and Sui framework types
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Map a struct from another package to an existing Rust type
+    ///
+    /// # Arguments
+    /// * `move_type` - The Move type without type params, e.g. "0xabc::pool::Pool"
+    /// * `rust_path` - The path of the Rust type, e.g. "crate::bindings::amm::pool::Pool"
+    ///
+    /// # Returns
+    /// * `Codegen` - The updated Codegen instance
+    pub fn with_extern_type(mut self, move_type: &str, rust_path: &str) -> Self {
+        self.extern_types.insert(format_coin_address(move_type), rust_path.to_string());
+        self
+    }
+
+    /// Generate bindings for a package published on chain
+    ///
+    /// # Arguments
+    /// * `client` - The client to fetch the package with
+    /// * `package_id` - The ID of the package
+    ///
+    /// # Returns
+    /// * `Result<String>` - The generated Rust source or an error
+    pub async fn generate_from_chain(&self, client: &SuiQueryZClient, package_id: ObjectID) -> Result<String> {
+        let modules = client.get_normalized_move_modules_by_package(package_id).await?;
+        let package = PackageModel::from_normalized(AccountAddress::from(package_id), &modules)?;
+        self.generate(&package)
+    }
+
+    /// Generate bindings for a local Move package
+    ///
+    /// # Arguments
+    /// * `path` - The directory containing the package's `Move.toml`
+    /// * `address` - The address the package is published at, defaults to the address it compiles with
+    ///
+    /// # Returns
+    /// * `Result<String>` - The generated Rust source or an error
+    pub fn generate_from_path(&self, path: &Path, address: Option<AccountAddress>) -> Result<String> {
+        let compiled_package = BuildConfig::new_for_testing().build(path)?;
+        let modules: Vec<_> = compiled_package.get_modules().collect();
+        let address = address
+            .or_else(|| modules.first().map(|module| *module.address()))
+            .ok_or_else(|| anyhow!("Package has no modules, path: {}", path.display()))?;
+
+        let package = PackageModel::from_compiled(address, modules)?;
+        self.generate(&package)
+    }
+
+    /// Generate bindings for a package model
+    ///
+    /// # Arguments
+    /// * `package` - The structs of the package
+    ///
+    /// # Returns
+    /// * `Result<String>` - The generated Rust source or an error if a field type has no Rust type
+    pub fn generate(&self, package: &PackageModel) -> Result<String> {
+        let mut out = String::new();
+        writeln!(out, "// Generated by queryz-codegen from package {}. Do not edit.", package.address.to_hex_literal())?;
+
+        for module in &package.modules {
+            if module.structs.is_empty() {
+                continue;
+            }
+            writeln!(out)?;
+            self.write_module(&mut out, package, module)?;
+        }
+        Ok(out)
+    }
+
+    fn write_module(&self, out: &mut String, package: &PackageModel, module: &ModuleModel) -> Result<()> {
+        writeln!(out, "#[allow(dead_code, non_camel_case_types, non_snake_case)]")?;
+        writeln!(out, "pub mod {} {{", rust_ident(&module.name))?;
+        for (index, move_struct) in module.structs.iter().enumerate() {
+            if index > 0 {
+                writeln!(out)?;
+            }
+            self.write_struct(out, package, module, move_struct)?;
+        }
+        writeln!(out, "}}")?;
+        Ok(())
+    }
+
+    fn write_struct(&self, out: &mut String, package: &PackageModel, module: &ModuleModel, move_struct: &StructModel) -> Result<()> {
+        let address = package.address.to_hex_literal();
+        let type_params: Vec<String> = (0..move_struct.type_params.len()).map(|index| format!("T{}", index)).collect();
+        let generics = if type_params.is_empty() { String::new() } else { format!("<{}>", type_params.join(", ")) };
+
+        writeln!(out, "    /// `{}::{}::{}`", address, module.name, move_struct.name)?;
+        writeln!(out, "    #[derive(Debug, {}::Deserialize, {}::MoveStruct)]", PRELUDE, PRELUDE)?;
+        writeln!(out, "    #[serde(crate = \"queryz::codegen::prelude::serde\")]")?;
+        writeln!(out, "    #[move_struct(address = \"{}\", module = \"{}\", name = \"{}\")]", address, module.name, move_struct.name)?;
+        writeln!(out, "    pub struct {}{} {{", move_struct.name, generics)?;
+
+        let mut used = vec![];
+        for (name, field_type) in &move_struct.fields {
+            field_type.collect_type_params(&mut used);
+            let rust_name = rust_ident(name);
+            if rust_name != *name {
+                writeln!(out, "        #[move_field(rename = \"{}\")]", name)?;
+            }
+            writeln!(out, "        pub {}: {},", rust_name, self.rust_type(package, field_type)?)?;
+        }
+
+        // Rust rejects unused type params, which Move allows for phantom params
+        let unused: Vec<&String> = type_params
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !used.contains(&(*index as u16)))
+            .map(|(_, param)| param)
+            .collect();
+        if !unused.is_empty() {
+            let phantom = unused.iter().map(|param| format!("{},", param)).collect::<String>();
+            writeln!(out, "        pub _phantom: ::std::marker::PhantomData<({})>,", phantom)?;
+        }
+        writeln!(out, "    }}")?;
+
+        self.write_collection_helpers(out, package, move_struct, &type_params, &generics)
+    }
+
+    /// Write helpers listing the entries of `Table` and `Bag` fields
+    fn write_collection_helpers(&self, out: &mut String, package: &PackageModel, move_struct: &StructModel, type_params: &[String], generics: &str) -> Result<()> {
+        let mut helpers = String::new();
+        for (name, field_type) in &move_struct.fields {
+            let FieldType::Struct { address, module, name: type_name, type_args } = field_type else {
+                continue;
+            };
+            if *address != AccountAddress::TWO {
+                continue;
+            }
+
+            let rust_name = rust_ident(name);
+            let method = format!("{}_entries", name);
+            match (module.as_str(), type_name.as_str(), type_args.as_slice()) {
+                ("table", "Table", [key, value]) => {
+                    let key = self.rust_type(package, key)?;
+                    let value = self.rust_type(package, value)?;
+                    writeln!(helpers, "        /// Entries of the `{}` table", name)?;
+                    writeln!(
+                        helpers,
+                        "        pub async fn {}(&self, query: &{}::BagQuery) -> {}::Result<::std::vec::Vec<{}::Field<{}, {}>>> {{",
+                        method, PRELUDE, PRELUDE, PRELUDE, key, value
+                    )?;
+                    writeln!(helpers, "            query.get_bag_typed_fields(*self.{}.id.object_id()).await", rust_name)?;
+                    writeln!(helpers, "        }}")?;
+                }
+                ("bag", "Bag", _) | ("object_bag", "ObjectBag", _) | ("object_table", "ObjectTable", _) => {
+                    writeln!(helpers, "        /// Entries of the `{}` collection, decoded from their on-chain layout", name)?;
+                    writeln!(
+                        helpers,
+                        "        pub async fn {}(&self, query: &{}::BagQuery) -> {}::Result<::std::vec::Vec<{}::BagEntry>> {{",
+                        method, PRELUDE, PRELUDE, PRELUDE
+                    )?;
+                    writeln!(helpers, "            query.get_bag_entries(*self.{}.id.object_id()).await", rust_name)?;
+                    writeln!(helpers, "        }}")?;
+                }
+                _ => {}
+            }
+        }
+        if helpers.is_empty() {
+            return Ok(());
+        }
+
+        let bounds = if type_params.is_empty() {
+            String::new()
+        } else {
+            let bounds: Vec<String> = type_params
+                .iter()
+                .map(|param| format!("{}: {}::MoveType + {}::DeserializeOwned", param, PRELUDE, PRELUDE))
+                .collect();
+            format!("<{}>", bounds.join(", "))
+        };
+        writeln!(out)?;
+        writeln!(out, "    impl{} {}{} {{", bounds, move_struct.name, generics)?;
+        write!(out, "{}", helpers)?;
+        writeln!(out, "    }}")?;
+        Ok(())
+    }
+
+    /// The Rust type of a field
+    fn rust_type(&self, package: &PackageModel, field_type: &FieldType) -> Result<String> {
+        let rust_type = match field_type {
+            FieldType::Bool => "bool".to_string(),
+            FieldType::U8 => "u8".to_string(),
+            FieldType::U16 => "u16".to_string(),
+            FieldType::U32 => "u32".to_string(),
+            FieldType::U64 => "u64".to_string(),
+            FieldType::U128 => "u128".to_string(),
+            FieldType::U256 => format!("{}::U256", PRELUDE),
+            FieldType::Address => format!("{}::SuiAddress", PRELUDE),
+            FieldType::TypeParam(index) => format!("T{}", index),
+            FieldType::Vector(inner) => format!("::std::vec::Vec<{}>", self.rust_type(package, inner)?),
+            FieldType::Struct { address, module, name, type_args } => {
+                let move_type = format!("{}::{}::{}", address.to_hex_literal(), module, name);
+                let path = if *address == package.address {
+                    let defining_module = package.modules.iter().find(|candidate| candidate.name == *module);
+                    if defining_module.is_some_and(|candidate| candidate.enums.contains(name)) {
+                        return Err(anyhow!("{} is a Move enum, which codegen does not support yet", move_type));
+                    }
+                    if !defining_module.is_some_and(|candidate| candidate.structs.iter().any(|s| s.name == *name)) {
+                        return Err(anyhow!("Struct {} not found in package", move_type));
+                    }
+                    format!("super::{}::{}", rust_ident(module), name)
+                } else {
+                    self.extern_types
+                        .get(&format_coin_address(&move_type))
+                        .cloned()
+                        .ok_or_else(|| anyhow!("No Rust type for {}, map it with Codegen::with_extern_type", move_type))?
+                };
+
+                if type_args.is_empty() {
+                    path
+                } else {
+                    let args = type_args
+                        .iter()
+                        .map(|arg| self.rust_type(package, arg))
+                        .collect::<Result<Vec<_>>>()?;
+                    format!("{}<{}>", path, args.join(", "))
+                }
+            }
+        };
+        Ok(rust_type)
+    }
+}
+
+/// Escape Rust keywords used as identifiers
+fn rust_ident(name: &str) -> String {
+    if RUST_KEYWORDS.contains(&name) {
+        format!("r#{}", name)
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_struct_with_table() {
+        let address = AccountAddress::from_hex_literal("0x42").unwrap();
+        let table = FieldType::Struct {
+            address: AccountAddress::TWO,
+            module: "table".to_string(),
+            name: "Table".to_string(),
+            type_args: vec![FieldType::Address, FieldType::U64],
+        };
+        let package = PackageModel {
+            address,
+            modules: vec![ModuleModel {
+                name: "pool".to_string(),
+                structs: vec![StructModel {
+                    name: "Pool".to_string(),
+                    type_params: vec![true],
+                    fields: vec![
+                        ("type".to_string(), FieldType::U8),
+                        ("positions".to_string(), table),
+                    ],
+                }],
+                enums: vec!["Status".to_string()],
+            }],
+        };
+
+        let source = Codegen::new().generate(&package).unwrap();
+        assert!(source.contains("pub struct Pool<T0> {"));
+        assert!(source.contains("#[move_field(rename = \"type\")]\n        pub r#type: u8,"));
+        assert!(source.contains("pub _phantom: ::std::marker::PhantomData<(T0,)>,"));
+        assert!(source.contains("pub async fn positions_entries(&self"));
+
+        let unknown = FieldType::Struct {
+            address: AccountAddress::from_hex_literal("0x99").unwrap(),
+            module: "amm".to_string(),
+            name: "Pool".to_string(),
+            type_args: vec![],
+        };
+        let mut package = package;
+        package.modules[0].structs[0].fields.push(("other".to_string(), unknown));
+        assert!(Codegen::new().generate(&package).is_err());
+        assert!(Codegen::new()
+            .with_extern_type("0x99::amm::Pool", "crate::amm::Pool")
+            .generate(&package)
+            .unwrap()
+            .contains("pub other: crate::amm::Pool,"));
+    }
+
+    #[test]
+    fn test_generate_rejects_enum_fields() {
+        let address = AccountAddress::from_hex_literal("0x42").unwrap();
+        let field_type = |module: &str, name: &str| FieldType::Struct {
+            address,
+            module: module.to_string(),
+            name: name.to_string(),
+            type_args: vec![],
+        };
+        let mut package = PackageModel {
+            address,
+            modules: vec![ModuleModel {
+                name: "pool".to_string(),
+                structs: vec![StructModel {
+                    name: "Pool".to_string(),
+                    type_params: vec![],
+                    fields: vec![("status".to_string(), field_type("pool", "Status"))],
+                }],
+                enums: vec!["Status".to_string()],
+            }],
+        };
+
+        let error = Codegen::new().generate(&package).unwrap_err();
+        assert!(error.to_string().contains("enum"));
+
+        package.modules[0].structs[0].fields = vec![("missing".to_string(), field_type("pool", "Missing"))];
+        assert!(Codegen::new().generate(&package).is_err());
+    }
+}
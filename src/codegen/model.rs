@@ -0,0 +1,354 @@
+use anyhow::{anyhow, Result};
+use move_binary_format::file_format::{CompiledModule, SignatureToken, StructFieldInformation};
+use move_core_types::account_address::AccountAddress;
+use std::collections::BTreeMap;
+use sui_json_rpc_types::{SuiMoveNormalizedModule, SuiMoveNormalizedType};
+
+/// The structs of a Move package, as needed to generate Rust bindings
+#[derive(Debug, Clone)]
+pub struct PackageModel {
+    /// The address of the package
+    pub address: AccountAddress,
+    /// The modules of the package, ordered by name
+    pub modules: Vec<ModuleModel>,
+}
+
+/// The structs of a Move module
+#[derive(Debug, Clone)]
+pub struct ModuleModel {
+    /// The module name
+    pub name: String,
+    /// The structs of the module
+    pub structs: Vec<StructModel>,
+    /// The names of the enums of the module, which are not generated
+    pub enums: Vec<String>,
+}
+
+/// A Move struct
+#[derive(Debug, Clone)]
+pub struct StructModel {
+    /// The struct name
+    pub name: String,
+    /// Whether each type param is phantom
+    pub type_params: Vec<bool>,
+    /// The fields in declaration order
+    pub fields: Vec<(String, FieldType)>,
+}
+
+/// The type of a struct field
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldType {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    U256,
+    Address,
+    Vector(Box<FieldType>),
+    Struct {
+        address: AccountAddress,
+        module: String,
+        name: String,
+        type_args: Vec<FieldType>,
+    },
+    /// A type param of the enclosing struct, by index
+    TypeParam(u16),
+}
+
+impl FieldType {
+    /// Collect the type params used by this type
+    pub fn collect_type_params(&self, used: &mut Vec<u16>) {
+        match self {
+            FieldType::TypeParam(index) if !used.contains(index) => used.push(*index),
+            FieldType::Vector(inner) => inner.collect_type_params(used),
+            FieldType::Struct { type_args, .. } => type_args.iter().for_each(|arg| arg.collect_type_params(used)),
+            _ => {}
+        }
+    }
+}
+
+impl PackageModel {
+    /// Build the model from normalized modules, as returned by `sui_getNormalizedMoveModulesByPackage`
+    ///
+    /// # Arguments
+    /// * `address` - The address of the package
+    /// * `modules` - The normalized modules using the module name as key
+    ///
+    /// # Returns
+    /// * `Result<PackageModel>` - The package model or an error
+    pub fn from_normalized(address: AccountAddress, modules: &BTreeMap<String, SuiMoveNormalizedModule>) -> Result<Self> {
+        let mut module_models = Vec::with_capacity(modules.len());
+        for (module_name, module) in modules {
+            let mut structs = Vec::with_capacity(module.structs.len());
+            for (struct_name, normalized) in &module.structs {
+                let mut fields = Vec::with_capacity(normalized.fields.len());
+                for field in &normalized.fields {
+                    fields.push((field.name.clone(), from_normalized_type(&field.type_)?));
+                }
+                structs.push(StructModel {
+                    name: struct_name.clone(),
+                    type_params: normalized.type_parameters.iter().map(|param| param.is_phantom).collect(),
+                    fields,
+                });
+            }
+            module_models.push(ModuleModel {
+                name: module_name.clone(),
+                structs,
+                enums: module.enums.keys().cloned().collect(),
+            });
+        }
+
+        Ok(Self {
+            address,
+            modules: module_models,
+        })
+    }
+
+    /// Build the model from compiled modules, e.g. of a package built locally with `sui-move-build`
+    ///
+    /// # Arguments
+    /// * `address` - The address the package is published at, replacing the address the modules were compiled with
+    /// * `modules` - The compiled modules of the package
+    ///
+    /// # Returns
+    /// * `Result<PackageModel>` - The package model or an error
+    pub fn from_compiled<'a>(address: AccountAddress, modules: impl IntoIterator<Item = &'a CompiledModule>) -> Result<Self> {
+        let mut module_models = vec![];
+        for module in modules {
+            let self_address = *module.address();
+            let mut structs = vec![];
+            for struct_def in module.struct_defs() {
+                let handle = module.datatype_handle_at(struct_def.struct_handle);
+                let StructFieldInformation::Declared(field_defs) = &struct_def.field_information else {
+                    return Err(anyhow!("Native struct {} is not supported", module.identifier_at(handle.name)));
+                };
+
+                let mut fields = Vec::with_capacity(field_defs.len());
+                for field in field_defs {
+                    let field_type = from_signature_token(module, &field.signature.0, self_address, address)?;
+                    fields.push((module.identifier_at(field.name).to_string(), field_type));
+                }
+                structs.push(StructModel {
+                    name: module.identifier_at(handle.name).to_string(),
+                    type_params: handle.type_parameters.iter().map(|param| param.is_phantom).collect(),
+                    fields,
+                });
+            }
+            let enums = module
+                .enum_defs()
+                .iter()
+                .map(|enum_def| module.identifier_at(module.datatype_handle_at(enum_def.enum_handle).name).to_string())
+                .collect();
+            module_models.push(ModuleModel {
+                name: module.name().to_string(),
+                structs,
+                enums,
+            });
+        }
+        module_models.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(Self {
+            address,
+            modules: module_models,
+        })
+    }
+}
+
+fn from_normalized_type(normalized: &SuiMoveNormalizedType) -> Result<FieldType> {
+    let field_type = match normalized {
+        SuiMoveNormalizedType::Bool => FieldType::Bool,
+        SuiMoveNormalizedType::U8 => FieldType::U8,
+        SuiMoveNormalizedType::U16 => FieldType::U16,
+        SuiMoveNormalizedType::U32 => FieldType::U32,
+        SuiMoveNormalizedType::U64 => FieldType::U64,
+        SuiMoveNormalizedType::U128 => FieldType::U128,
+        SuiMoveNormalizedType::U256 => FieldType::U256,
+        SuiMoveNormalizedType::Address | SuiMoveNormalizedType::Signer => FieldType::Address,
+        SuiMoveNormalizedType::Vector(inner) => FieldType::Vector(Box::new(from_normalized_type(inner)?)),
+        SuiMoveNormalizedType::TypeParameter(index) => FieldType::TypeParam(*index),
+        SuiMoveNormalizedType::Struct { inner } => FieldType::Struct {
+            address: AccountAddress::from_hex_literal(&inner.address)?,
+            module: inner.module.clone(),
+            name: inner.name.clone(),
+            type_args: inner.type_arguments.iter().map(from_normalized_type).collect::<Result<_>>()?,
+        },
+        SuiMoveNormalizedType::Reference(_) | SuiMoveNormalizedType::MutableReference(_) => {
+            return Err(anyhow!("References cannot be struct fields"));
+        }
+    };
+    Ok(field_type)
+}
+
+/// Convert a field type of a compiled module, replacing its own address with the published address
+fn from_signature_token(module: &CompiledModule, token: &SignatureToken, self_address: AccountAddress, address: AccountAddress) -> Result<FieldType> {
+    let struct_type = |handle_index, type_args: &[SignatureToken]| -> Result<FieldType> {
+        let handle = module.datatype_handle_at(handle_index);
+        let module_handle = module.module_handle_at(handle.module);
+        let struct_address = *module.address_identifier_at(module_handle.address);
+        Ok(FieldType::Struct {
+            address: if struct_address == self_address { address } else { struct_address },
+            module: module.identifier_at(module_handle.name).to_string(),
+            name: module.identifier_at(handle.name).to_string(),
+            type_args: type_args
+                .iter()
+                .map(|arg| from_signature_token(module, arg, self_address, address))
+                .collect::<Result<_>>()?,
+        })
+    };
+
+    let field_type = match token {
+        SignatureToken::Bool => FieldType::Bool,
+        SignatureToken::U8 => FieldType::U8,
+        SignatureToken::U16 => FieldType::U16,
+        SignatureToken::U32 => FieldType::U32,
+        SignatureToken::U64 => FieldType::U64,
+        SignatureToken::U128 => FieldType::U128,
+        SignatureToken::U256 => FieldType::U256,
+        SignatureToken::Address | SignatureToken::Signer => FieldType::Address,
+        SignatureToken::Vector(inner) => FieldType::Vector(Box::new(from_signature_token(module, inner, self_address, address)?)),
+        SignatureToken::TypeParameter(index) => FieldType::TypeParam(*index),
+        SignatureToken::Datatype(handle_index) => struct_type(*handle_index, &[])?,
+        SignatureToken::DatatypeInstantiation(instantiation) => {
+            let (handle_index, type_args) = instantiation.as_ref();
+            struct_type(*handle_index, type_args)?
+        }
+        SignatureToken::Reference(_) | SignatureToken::MutableReference(_) => {
+            return Err(anyhow!("References cannot be struct fields"));
+        }
+    };
+    Ok(field_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_binary_format::file_format::{
+        empty_module, AbilitySet, AddressIdentifierIndex, DatatypeHandle, DatatypeHandleIndex, DatatypeTyParameter,
+        FieldDefinition, IdentifierIndex, ModuleHandle, ModuleHandleIndex, StructDefinition, TypeSignature,
+    };
+    use move_core_types::identifier::Identifier;
+
+    #[test]
+    fn test_from_normalized() {
+        let module: SuiMoveNormalizedModule = serde_json::from_value(serde_json::json!({
+            "fileFormatVersion": 6,
+            "address": "0x42",
+            "name": "pool",
+            "friends": [],
+            "structs": {
+                "Pool": {
+                    "abilities": { "abilities": ["Key"] },
+                    "typeParameters": [{ "constraints": { "abilities": [] }, "isPhantom": true }],
+                    "fields": [
+                        { "name": "id", "type": { "Struct": { "address": "0x2", "module": "object", "name": "UID", "typeArguments": [] } } },
+                        { "name": "amounts", "type": { "Vector": { "TypeParameter": 0 } } }
+                    ]
+                }
+            },
+            "enums": {
+                "Status": {
+                    "abilities": { "abilities": ["Store"] },
+                    "typeParameters": [],
+                    "variants": { "Active": [] }
+                }
+            },
+            "exposedFunctions": {}
+        }))
+        .unwrap();
+        let modules = BTreeMap::from([("pool".to_string(), module)]);
+
+        let address = AccountAddress::from_hex_literal("0x42").unwrap();
+        let package = PackageModel::from_normalized(address, &modules).unwrap();
+        let pool = &package.modules[0];
+        assert_eq!(pool.name, "pool");
+        assert_eq!(pool.enums, vec!["Status".to_string()]);
+        assert_eq!(pool.structs[0].type_params, vec![true]);
+        assert_eq!(
+            pool.structs[0].fields,
+            vec![
+                (
+                    "id".to_string(),
+                    FieldType::Struct {
+                        address: AccountAddress::TWO,
+                        module: "object".to_string(),
+                        name: "UID".to_string(),
+                        type_args: vec![],
+                    }
+                ),
+                ("amounts".to_string(), FieldType::Vector(Box::new(FieldType::TypeParam(0)))),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_from_compiled() {
+        // `struct Pool<phantom T> { value: u64, balance: 0x2::balance::Balance<T> }` compiled at 0x0
+        let mut module = empty_module();
+        let identifier = |module: &mut CompiledModule, name: &str| {
+            module.identifiers.push(Identifier::new(name).unwrap());
+            IdentifierIndex((module.identifiers.len() - 1) as u16)
+        };
+        let pool_name = identifier(&mut module, "Pool");
+        let value_name = identifier(&mut module, "value");
+        let balance_field_name = identifier(&mut module, "balance");
+        let balance_module_name = identifier(&mut module, "balance");
+        let balance_name = identifier(&mut module, "Balance");
+
+        module.address_identifiers.push(AccountAddress::TWO);
+        module.module_handles.push(ModuleHandle {
+            address: AddressIdentifierIndex(1),
+            name: balance_module_name,
+        });
+        let phantom = || DatatypeTyParameter {
+            constraints: AbilitySet::EMPTY,
+            is_phantom: true,
+        };
+        module.datatype_handles.push(DatatypeHandle {
+            module: ModuleHandleIndex(0),
+            name: pool_name,
+            abilities: AbilitySet::EMPTY,
+            type_parameters: vec![phantom()],
+        });
+        module.datatype_handles.push(DatatypeHandle {
+            module: ModuleHandleIndex(1),
+            name: balance_name,
+            abilities: AbilitySet::EMPTY,
+            type_parameters: vec![phantom()],
+        });
+        module.struct_defs.push(StructDefinition {
+            struct_handle: DatatypeHandleIndex(0),
+            field_information: StructFieldInformation::Declared(vec![
+                FieldDefinition {
+                    name: value_name,
+                    signature: TypeSignature(SignatureToken::U64),
+                },
+                FieldDefinition {
+                    name: balance_field_name,
+                    signature: TypeSignature(SignatureToken::DatatypeInstantiation(Box::new((
+                        DatatypeHandleIndex(1),
+                        vec![SignatureToken::TypeParameter(0)],
+                    )))),
+                },
+            ]),
+        });
+
+        let address = AccountAddress::from_hex_literal("0x42").unwrap();
+        let package = PackageModel::from_compiled(address, [&module]).unwrap();
+        let pool = &package.modules[0].structs[0];
+        assert_eq!(pool.name, "Pool");
+        assert_eq!(pool.type_params, vec![true]);
+        assert_eq!(pool.fields[0], ("value".to_string(), FieldType::U64));
+        assert_eq!(
+            pool.fields[1].1,
+            FieldType::Struct {
+                address: AccountAddress::TWO,
+                module: "balance".to_string(),
+                name: "Balance".to_string(),
+                type_args: vec![FieldType::TypeParam(0)],
+            }
+        );
+        assert!(package.modules[0].enums.is_empty());
+    }
+}
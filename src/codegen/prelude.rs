@@ -0,0 +1,17 @@
+// Items referenced by generated bindings, so they only need `queryz` as a dependency
+
+pub use anyhow::Result;
+pub use move_core_types::u256::U256;
+pub use serde;
+pub use serde::de::DeserializeOwned;
+pub use serde::Deserialize;
+pub use std::marker::PhantomData;
+pub use sui_types::base_types::{ObjectID, SuiAddress};
+pub use sui_types::dynamic_field::Field;
+pub use sui_types::id::{ID, UID};
+
+pub use super::framework::*;
+pub use crate::query::bag::BagQuery;
+pub use crate::types::bag::BagEntry;
+pub use crate::types::move_type::{AsciiString, MoveStruct, MoveType};
+pub use crate::types::type_name::TypeName;
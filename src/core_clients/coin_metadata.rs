@@ -1,17 +1,182 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
 use tokio::sync::RwLock;
 use std::collections::HashMap;
-use std::sync::Arc;
-use sui_json_rpc_types::SuiCoinMetadata;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use futures::future::{BoxFuture, FutureExt, Shared, TryFutureExt};
+use futures::{stream, StreamExt};
+use sui_json_rpc_types::{SuiCoinMetadata, SuiObjectDataOptions};
 use sui_sdk::SuiClient;
+use sui_types::object::Owner;
 
-/// CoinMetadataClient manages coin metadata
-/// Contains a cache and SUI client to fetch metadata from chain or read from cache
-pub struct CoinMetadataClient {
+use crate::core_clients::metadata_store::{CoinMetadataStore, StoredMetadata};
+use crate::core_clients::token_registry::{TokenEntry, TokenRegistry};
+
+/// Number of coin types fetched at the same time by `CoinMetadataClient::preload`
+pub const DEFAULT_PRELOAD_CONCURRENCY: usize = 16;
+
+/// Cache settings of a CoinMetadataClient
+#[derive(Debug, Clone)]
+pub struct CoinMetadataCacheConfig {
+    /// How long metadata stays fresh, `None` to never expire
+    ///
+    /// Metadata whose on-chain object is frozen can never change and does not expire.
+    pub ttl: Option<Duration>,
+    /// How long a "metadata not found" result is remembered
+    pub negative_ttl: Duration,
+    /// Maximum number of cached entries, least recently used entries are evicted first
+    pub max_entries: Option<usize>,
+}
+
+impl Default for CoinMetadataCacheConfig {
+    fn default() -> Self {
+        Self {
+            ttl: None,
+            negative_ttl: Duration::from_secs(300),
+            max_entries: None,
+        }
+    }
+}
+
+/// Outcome of preloading coin metadata
+#[derive(Debug, Clone, Default)]
+pub struct PreloadReport {
+    /// Coin types whose metadata is now cached
+    pub loaded: Vec<String>,
+    /// Coin types that have no metadata
+    pub missing: Vec<String>,
+    /// Coin types whose fetch failed, with the error message
+    pub failed: Vec<(String, String)>,
+}
+
+/// A cached lookup result
+struct CacheEntry {
+    /// The metadata, `None` if the coin has no metadata
+    metadata: Option<SuiCoinMetadata>,
+    /// When the entry was fetched, wall clock time so it can be persisted
+    fetched_at: SystemTime,
+    /// Whether the entry never expires, because its metadata object is frozen or it was set manually
+    permanent: bool,
+    /// Logical time of the last read, used for LRU eviction
+    last_used: AtomicU64,
+}
+
+/// Metadata fetched from chain
+#[derive(Debug, Clone)]
+pub struct FetchedMetadata {
+    /// The metadata, `None` if the coin has no metadata
+    pub metadata: Option<SuiCoinMetadata>,
+    /// Whether the metadata object is frozen, so the metadata can never change
+    pub frozen: bool,
+}
+
+/// Trait for fetching coin metadata on cache misses
+///
+/// Implemented over RPC by `RpcMetadataFetcher`; tests can provide their own implementation.
+#[async_trait]
+pub trait MetadataFetcher: Send + Sync {
+    /// Fetch the metadata of a coin type
+    ///
+    /// # Arguments
+    /// * `coin_type` - Coin type string, e.g. "0x2::sui::SUI"
+    /// * `check_frozen` - Whether to look up if the metadata object is frozen
+    ///
+    /// # Returns
+    /// * `Result<FetchedMetadata>` - Coin metadata, `None` if not found, or error
+    async fn fetch_metadata(&self, coin_type: &str, check_frozen: bool) -> Result<FetchedMetadata>;
+}
+
+/// MetadataFetcher backed by the coin and object RPC methods of a fullnode
+pub struct RpcMetadataFetcher {
     /// SUI client
     client: Arc<SuiClient>,
+}
+
+impl RpcMetadataFetcher {
+    /// Create a new RpcMetadataFetcher
+    ///
+    /// # Arguments
+    /// * `client` - SUI client used for the RPC calls
+    ///
+    /// # Returns
+    /// * `RpcMetadataFetcher` - A new fetcher instance
+    pub fn new(client: Arc<SuiClient>) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl MetadataFetcher for RpcMetadataFetcher {
+    async fn fetch_metadata(&self, coin_type: &str, check_frozen: bool) -> Result<FetchedMetadata> {
+        let metadata = self.client.coin_read_api().get_coin_metadata(coin_type.to_string()).await?;
+
+        let mut frozen = false;
+        let metadata_id = metadata.as_ref().and_then(|metadata| metadata.id);
+        if let Some(object_id) = metadata_id.filter(|_| check_frozen) {
+            let resp = self
+                .client
+                .read_api()
+                .get_object_with_options(object_id, SuiObjectDataOptions::new().with_owner())
+                .await?;
+            frozen = matches!(resp.data.and_then(|data| data.owner), Some(Owner::Immutable));
+        }
+
+        Ok(FetchedMetadata { metadata, frozen })
+    }
+}
+
+/// A fetch from chain that concurrent callers for the same coin type can await together
+#[derive(Clone)]
+struct InflightFetch {
+    /// The fetch, driven by whichever caller polls it
+    result: Shared<BoxFuture<'static, Result<FetchedMetadata, Arc<anyhow::Error>>>>,
+    /// Set when the caller that started the fetch was cancelled before updating the cache
+    orphaned: Arc<AtomicBool>,
+}
+
+/// Removes an in-flight fetch from the in-flight map when dropped
+///
+/// If dropped before `completed` is set, the fetch is marked as orphaned so that a
+/// caller still awaiting it updates the cache instead.
+struct InflightGuard<'a> {
+    inflight: &'a Mutex<HashMap<String, InflightFetch>>,
+    coin_type: &'a str,
+    fetch: InflightFetch,
+    completed: bool,
+}
+
+impl Drop for InflightGuard<'_> {
+    fn drop(&mut self) {
+        // Never panic here, this may run while unwinding
+        let mut inflight = self.inflight.lock().unwrap_or_else(|e| e.into_inner());
+        if inflight.get(self.coin_type).is_some_and(|fetch| fetch.result.ptr_eq(&self.fetch.result)) {
+            inflight.remove(self.coin_type);
+        }
+        if !self.completed {
+            self.fetch.orphaned.store(true, Ordering::SeqCst);
+        }
+    }
+}
+
+/// CoinMetadataClient manages coin metadata
+/// Contains a cache and a fetcher to fetch metadata from chain or read from cache
+pub struct CoinMetadataClient {
+    /// Fetcher used on cache misses
+    fetcher: Arc<dyn MetadataFetcher>,
     /// Cache using coin type address as key
-    cache: RwLock<HashMap<String, SuiCoinMetadata>>,
+    cache: RwLock<HashMap<String, CacheEntry>>,
+    /// Persistent store the cache is loaded from and written through to
+    store: Option<Arc<dyn CoinMetadataStore>>,
+    /// Cache settings
+    config: CoinMetadataCacheConfig,
+    /// Logical clock for LRU eviction
+    clock: AtomicU64,
+    /// Fetches currently running, using coin type as key
+    inflight: Mutex<HashMap<String, InflightFetch>>,
+    /// Registry consulted before the cache and the chain
+    token_registry: Arc<TokenRegistry>,
 }
 
 impl CoinMetadataClient {
@@ -23,10 +188,106 @@ impl CoinMetadataClient {
     /// # Returns
     /// * `CoinMetadataClient` - A new manager instance
     pub fn new(client: Arc<SuiClient>) -> Self {
+        Self::from_fetcher(Arc::new(RpcMetadataFetcher::new(client)))
+    }
+
+    /// Create a new CoinMetadataClient using a custom fetcher, e.g. a mock in tests
+    ///
+    /// # Arguments
+    /// * `fetcher` - The fetcher to use on cache misses
+    ///
+    /// # Returns
+    /// * `CoinMetadataClient` - A new manager instance
+    pub fn from_fetcher(fetcher: Arc<dyn MetadataFetcher>) -> Self {
         Self {
-            client,
+            fetcher,
             cache: RwLock::new(HashMap::new()),
+            store: None,
+            config: CoinMetadataCacheConfig::default(),
+            clock: AtomicU64::new(0),
+            inflight: Mutex::new(HashMap::new()),
+            token_registry: Arc::new(TokenRegistry::new()),
+        }
+    }
+
+    /// Back the cache with a persistent store
+    ///
+    /// The cache is filled from the store and newly fetched metadata is written through to it.
+    /// Loaded entries keep their original fetch time, so stale entries are fetched again.
+    ///
+    /// # Arguments
+    /// * `store` - Store persisting the cache across restarts
+    ///
+    /// # Returns
+    /// * `Result<CoinMetadataClient>` - The updated manager instance or an error if the store cannot be loaded
+    pub fn with_store(mut self, store: Arc<dyn CoinMetadataStore>) -> Result<Self> {
+        let cache = self.cache.get_mut();
+        for (coin_type, stored) in store.load()? {
+            let entry = CacheEntry {
+                metadata: Some(stored.metadata),
+                fetched_at: stored.fetched_at,
+                permanent: stored.permanent,
+                last_used: AtomicU64::new(0),
+            };
+            cache.insert(coin_type, entry);
         }
+        self.store = Some(store);
+        Ok(self)
+    }
+
+    /// Use different cache settings
+    ///
+    /// # Arguments
+    /// * `config` - The cache settings
+    ///
+    /// # Returns
+    /// * `CoinMetadataClient` - The updated manager instance
+    pub fn with_cache_config(mut self, config: CoinMetadataCacheConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Use a token registry whose entries take precedence over on-chain metadata
+    ///
+    /// # Arguments
+    /// * `token_registry` - The registry to consult before the cache and the chain
+    ///
+    /// # Returns
+    /// * `CoinMetadataClient` - The updated manager instance
+    pub fn with_token_registry(mut self, token_registry: Arc<TokenRegistry>) -> Self {
+        self.token_registry = token_registry;
+        self
+    }
+
+    /// Get the fetcher used on cache misses
+    pub fn fetcher(&self) -> Arc<dyn MetadataFetcher> {
+        self.fetcher.clone()
+    }
+
+    /// Get the cache settings
+    pub fn cache_config(&self) -> &CoinMetadataCacheConfig {
+        &self.config
+    }
+
+    /// Get the persistent store, if any
+    pub fn store(&self) -> Option<Arc<dyn CoinMetadataStore>> {
+        self.store.clone()
+    }
+
+    /// Get the token registry
+    pub fn token_registry(&self) -> Arc<TokenRegistry> {
+        self.token_registry.clone()
+    }
+
+    /// Get the registry entry of a coin type
+    ///
+    /// # Arguments
+    /// * `coin_type` - Coin type string, e.g. "0x2::sui::SUI"
+    ///
+    /// # Returns
+    /// * `Option<&TokenEntry>` - The entry, `None` if the coin type is not in the registry
+    pub fn token_entry(&self, coin_type: &str) -> Option<&TokenEntry> {
+        self.token_registry.get(coin_type)
     }
 
     /// Get coin metadata
@@ -37,50 +298,491 @@ impl CoinMetadataClient {
     /// # Returns
     /// * `Result<CoinMetadata>` - Coin metadata or error
     pub async fn get_metadata(&self, coin_type: &str) -> Result<SuiCoinMetadata> {
-        // First check the cache
+        self.try_get_metadata(coin_type)
+            .await?
+            .ok_or_else(|| anyhow!("Coin metadata not found, coin_type: {}", coin_type))
+    }
+
+    /// Get coin metadata, distinguishing a missing metadata object from an RPC error
+    ///
+    /// # Arguments
+    /// * `coin_type` - Coin type string, e.g. "0x2::sui::SUI"
+    ///
+    /// # Returns
+    /// * `Result<Option<CoinMetadata>>` - Coin metadata, `None` if the coin has no metadata, or error
+    pub async fn try_get_metadata(&self, coin_type: &str) -> Result<Option<SuiCoinMetadata>> {
+        // Registry entries override whatever is on chain
+        if let Some(entry) = self.token_registry.get(coin_type) {
+            let mut metadata = entry.to_metadata();
+            // Keep the on-chain logo unless the registry pins one; a failed lookup only loses the logo
+            if metadata.icon_url.is_none() {
+                if let Ok(Some(on_chain)) = self.try_get_on_chain_metadata(coin_type).await {
+                    metadata.icon_url = on_chain.icon_url;
+                }
+            }
+            return Ok(Some(metadata));
+        }
+
+        self.try_get_on_chain_metadata(coin_type).await
+    }
+
+    /// Get on-chain coin metadata from the cache, fetching it on a miss
+    async fn try_get_on_chain_metadata(&self, coin_type: &str) -> Result<Option<SuiCoinMetadata>> {
+        // Check the cache first
         {
             let cache = self.cache.read().await;
-            if let Some(metadata) = cache.get(coin_type) {
-                return Ok(metadata.clone());
+            if let Some(entry) = cache.get(coin_type) {
+                if self.is_fresh(entry) {
+                    entry.last_used.store(self.tick(), Ordering::Relaxed);
+                    return Ok(entry.metadata.clone());
+                }
             }
         }
 
-        // Not in cache, fetch from chain
-        let metadata = self.fetch_metadata_from_chain(coin_type).await?;
-        
-        // Update cache
-        {
-            let mut cache = self.cache.write().await;
-            cache.insert(coin_type.to_string(), metadata.clone());
+        // Not in cache or expired, fetch from chain
+        self.fetch_coalesced(coin_type, self.config.ttl.is_some()).await
+    }
+
+    /// Fetch the metadata of many coin types into the cache
+    ///
+    /// Uses `DEFAULT_PRELOAD_CONCURRENCY` parallel fetches; see `preload_with_concurrency`.
+    ///
+    /// # Arguments
+    /// * `coin_types` - Coin type strings, e.g. "0x2::sui::SUI"
+    ///
+    /// # Returns
+    /// * `PreloadReport` - Which coin types were loaded, have no metadata or failed
+    pub async fn preload(&self, coin_types: &[String]) -> PreloadReport {
+        self.preload_with_concurrency(coin_types, DEFAULT_PRELOAD_CONCURRENCY).await
+    }
+
+    /// Fetch the metadata of many coin types into the cache with bounded parallelism
+    ///
+    /// Coin types that are already cached are not fetched again. A failed fetch does not
+    /// stop the others; it is reported in `PreloadReport::failed`.
+    ///
+    /// # Arguments
+    /// * `coin_types` - Coin type strings, e.g. "0x2::sui::SUI"
+    /// * `concurrency` - Maximum number of fetches in flight
+    ///
+    /// # Returns
+    /// * `PreloadReport` - Which coin types were loaded, have no metadata or failed
+    pub async fn preload_with_concurrency(&self, coin_types: &[String], concurrency: usize) -> PreloadReport {
+        let results: Vec<(String, Result<Option<SuiCoinMetadata>>)> = stream::iter(coin_types)
+            .map(|coin_type| async move { (coin_type.clone(), self.try_get_metadata(coin_type).await) })
+            .buffer_unordered(concurrency.max(1))
+            .collect()
+            .await;
+
+        let mut report = PreloadReport::default();
+        for (coin_type, result) in results {
+            match result {
+                Ok(Some(_)) => report.loaded.push(coin_type),
+                Ok(None) => report.missing.push(coin_type),
+                Err(e) => report.failed.push((coin_type, format!("{:#}", e))),
+            }
         }
-        
-        Ok(metadata)
+        report
     }
 
-    /// Fetch coin metadata from the blockchain
+    /// Fetch coin metadata from chain, replacing any cached entry
     ///
     /// # Arguments
-    /// * `coin_type` - Coin type string
+    /// * `coin_type` - Coin type string, e.g. "0x2::sui::SUI"
     ///
     /// # Returns
-    /// * `Result<CoinMetadata>` - Coin metadata or error
-    async fn fetch_metadata_from_chain(&self, coin_type: &str) -> Result<SuiCoinMetadata> {
-        let resp = self.client.coin_read_api().get_coin_metadata(coin_type.to_string()).await?;
-        match resp {
-            Some(metadata) => Ok(metadata),
-            None => Err(anyhow::anyhow!("Coin metadata not found, coin_type: {}", coin_type)),
+    /// * `Result<Option<CoinMetadata>>` - Coin metadata, `None` if the coin has no metadata, or error
+    pub async fn refresh_metadata(&self, coin_type: &str) -> Result<Option<SuiCoinMetadata>> {
+        let fetched = self.fetcher.fetch_metadata(coin_type, true).await?;
+        self.insert_entry(coin_type, fetched).await
+    }
+
+    /// Re-fetch all cached metadata whose metadata object may still change
+    ///
+    /// Entries whose metadata object is frozen or that were set manually are kept as they are.
+    ///
+    /// # Returns
+    /// * `Result<usize>` - The number of refreshed entries or an error
+    pub async fn refresh_mutable_metadata(&self) -> Result<usize> {
+        let coin_types: Vec<String> = {
+            let cache = self.cache.read().await;
+            cache
+                .iter()
+                .filter(|(_, entry)| entry.metadata.is_some() && !entry.permanent)
+                .map(|(coin_type, _)| coin_type.clone())
+                .collect()
+        };
+
+        for coin_type in &coin_types {
+            self.refresh_metadata(coin_type).await?;
+        }
+        Ok(coin_types.len())
+    }
+
+    /// Fetch coin metadata from chain, sharing the request with concurrent callers for the same coin type
+    ///
+    /// The first caller starts the fetch and updates the cache; later callers await its result.
+    /// If the first caller is cancelled, the next caller to receive the result updates the cache.
+    async fn fetch_coalesced(&self, coin_type: &str, check_frozen: bool) -> Result<Option<SuiCoinMetadata>> {
+        let (fetch, is_leader) = {
+            let mut inflight = self.inflight.lock().unwrap_or_else(|e| e.into_inner());
+            match inflight.get(coin_type) {
+                Some(fetch) => (fetch.clone(), false),
+                None => {
+                    let fetcher = self.fetcher.clone();
+                    let owned_coin_type = coin_type.to_string();
+                    let result = async move { fetcher.fetch_metadata(&owned_coin_type, check_frozen).await }
+                        .map_err(Arc::new)
+                        .boxed()
+                        .shared();
+                    let fetch = InflightFetch {
+                        result,
+                        orphaned: Arc::new(AtomicBool::new(false)),
+                    };
+                    inflight.insert(coin_type.to_string(), fetch.clone());
+                    (fetch, true)
+                }
+            }
+        };
+
+        // Removes the in-flight entry once the cache is updated, or if this caller is cancelled
+        let mut guard = is_leader.then(|| InflightGuard {
+            inflight: &self.inflight,
+            coin_type,
+            fetch: fetch.clone(),
+            completed: false,
+        });
+
+        let fetched = fetch.result.clone().await.map_err(|e| anyhow!("{:#}", e))?;
+
+        // A follower only updates the cache when it takes over from a cancelled leader
+        if !is_leader && !fetch.orphaned.swap(false, Ordering::SeqCst) {
+            return Ok(fetched.metadata);
+        }
+        let result = self.insert_entry(coin_type, fetched).await;
+        if let Some(guard) = &mut guard {
+            guard.completed = true;
         }
+        result
     }
 
-    /// Clear the cache
-    pub async fn clear_cache(&self) {
+    /// Insert a fetched result into the cache and the persistent store
+    ///
+    /// The store only persists the cache, so a failed write is logged and does not fail the lookup.
+    async fn insert_entry(&self, coin_type: &str, fetched: FetchedMetadata) -> Result<Option<SuiCoinMetadata>> {
+        let fetched_at = SystemTime::now();
+        if let Some(store) = self.store.clone() {
+            let owned_coin_type = coin_type.to_string();
+            let stored = fetched.metadata.clone().map(|metadata| StoredMetadata {
+                metadata,
+                fetched_at,
+                permanent: fetched.frozen,
+            });
+            // Coins without metadata are removed, so a stale entry does not come back after a restart
+            let result = tokio::task::spawn_blocking(move || match stored {
+                Some(stored) => store.save(&owned_coin_type, &stored),
+                None => store.remove(&owned_coin_type),
+            })
+            .await
+            .map_err(anyhow::Error::from)
+            .and_then(|result| result);
+            if let Err(e) = result {
+                tracing::warn!("Failed to persist coin metadata, coin_type: {}: {:#}", coin_type, e);
+            }
+        }
+
+        let entry = CacheEntry {
+            metadata: fetched.metadata.clone(),
+            fetched_at,
+            permanent: fetched.frozen,
+            last_used: AtomicU64::new(self.tick()),
+        };
         let mut cache = self.cache.write().await;
-        cache.clear();
+        cache.insert(coin_type.to_string(), entry);
+        self.evict(&mut cache);
+
+        Ok(fetched.metadata)
     }
-    
-    /// Manually add or update metadata in cache
-    pub async fn update_cache(&self, coin_type: &str, metadata: SuiCoinMetadata) {
+
+    /// Check whether a cached entry can still be used
+    fn is_fresh(&self, entry: &CacheEntry) -> bool {
+        // A clock set backwards makes the entry look new rather than failing the lookup
+        let age = entry.fetched_at.elapsed().unwrap_or_default();
+        match (&entry.metadata, self.config.ttl) {
+            (None, _) => age < self.config.negative_ttl,
+            (Some(_), _) if entry.permanent => true,
+            (Some(_), Some(ttl)) => age < ttl,
+            (Some(_), None) => true,
+        }
+    }
+
+    /// Evict least recently used entries until the cache fits its size bound
+    fn evict(&self, cache: &mut HashMap<String, CacheEntry>) {
+        let Some(max_entries) = self.config.max_entries else {
+            return;
+        };
+
+        while cache.len() > max_entries {
+            let oldest = cache
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used.load(Ordering::Relaxed))
+                .map(|(coin_type, _)| coin_type.clone());
+            match oldest {
+                Some(coin_type) => cache.remove(&coin_type),
+                None => break,
+            };
+        }
+    }
+
+    /// Advance the logical LRU clock
+    fn tick(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    /// Clear the cache and the persistent store
+    pub async fn clear_cache(&self) -> Result<()> {
         let mut cache = self.cache.write().await;
-        cache.insert(coin_type.to_string(), metadata);
+        cache.clear();
+        if let Some(store) = self.store.clone() {
+            tokio::task::spawn_blocking(move || store.clear()).await??;
+        }
+        Ok(())
+    }
+
+    /// Write out store entries whose write was deferred
+    ///
+    /// Stores such as `FileMetadataStore` batch their writes; call this before
+    /// shutting down to make sure every fetched entry is persisted.
+    pub async fn flush_store(&self) -> Result<()> {
+        if let Some(store) = self.store.clone() {
+            tokio::task::spawn_blocking(move || store.flush()).await??;
+        }
+        Ok(())
+    }
+
+    /// Manually add or update metadata in cache and the persistent store
+    ///
+    /// Manually set entries never expire.
+    pub async fn update_cache(&self, coin_type: &str, metadata: SuiCoinMetadata) -> Result<()> {
+        self.insert_entry(coin_type, FetchedMetadata { metadata: Some(metadata), frozen: true }).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_clients::metadata_store::InMemoryMetadataStore;
+    use std::sync::atomic::AtomicUsize;
+
+    struct MockFetcher {
+        calls: AtomicUsize,
+        delay: Duration,
+    }
+
+    #[async_trait]
+    impl MetadataFetcher for MockFetcher {
+        async fn fetch_metadata(&self, coin_type: &str, _check_frozen: bool) -> Result<FetchedMetadata> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(self.delay).await;
+            let metadata = (!coin_type.contains("missing")).then(|| SuiCoinMetadata {
+                decimals: 9,
+                name: coin_type.to_string(),
+                symbol: coin_type.rsplit("::").next().unwrap_or_default().to_string(),
+                description: String::new(),
+                icon_url: Some(format!("https://example.com/{}.png", coin_type)),
+                id: None,
+            });
+            Ok(FetchedMetadata { metadata, frozen: false })
+        }
+    }
+
+    fn mock_client(config: CoinMetadataCacheConfig) -> (Arc<MockFetcher>, CoinMetadataClient) {
+        let fetcher = Arc::new(MockFetcher {
+            calls: AtomicUsize::new(0),
+            delay: Duration::from_millis(20),
+        });
+        let client = CoinMetadataClient::from_fetcher(fetcher.clone()).with_cache_config(config);
+        (fetcher, client)
+    }
+
+    #[test]
+    fn test_is_fresh() {
+        let (_, client) = mock_client(CoinMetadataCacheConfig {
+            ttl: Some(Duration::from_secs(60)),
+            negative_ttl: Duration::from_secs(10),
+            max_entries: None,
+        });
+        let entry = |metadata: Option<SuiCoinMetadata>, age: Duration, permanent: bool| CacheEntry {
+            metadata,
+            fetched_at: SystemTime::now() - age,
+            permanent,
+            last_used: AtomicU64::new(0),
+        };
+        let metadata = || Some(SuiCoinMetadata {
+            decimals: 9,
+            name: "Sui".to_string(),
+            symbol: "SUI".to_string(),
+            description: String::new(),
+            icon_url: None,
+            id: None,
+        });
+
+        assert!(client.is_fresh(&entry(metadata(), Duration::from_secs(30), false)));
+        assert!(!client.is_fresh(&entry(metadata(), Duration::from_secs(90), false)));
+        assert!(client.is_fresh(&entry(metadata(), Duration::from_secs(90), true)));
+        assert!(client.is_fresh(&entry(None, Duration::from_secs(5), false)));
+        assert!(!client.is_fresh(&entry(None, Duration::from_secs(30), false)));
+    }
+
+    #[tokio::test]
+    async fn test_missing_metadata_is_cached() {
+        let (fetcher, client) = mock_client(CoinMetadataCacheConfig::default());
+
+        assert!(client.try_get_metadata("0x1::missing::MISSING").await.unwrap().is_none());
+        assert!(client.try_get_metadata("0x1::missing::MISSING").await.unwrap().is_none());
+        assert!(client.get_metadata("0x1::missing::MISSING").await.is_err());
+        assert_eq!(fetcher.calls.load(Ordering::SeqCst), 1);
+
+        // An expired negative entry is fetched again
+        let (fetcher, client) = mock_client(CoinMetadataCacheConfig {
+            negative_ttl: Duration::ZERO,
+            ..Default::default()
+        });
+        client.try_get_metadata("0x1::missing::MISSING").await.unwrap();
+        client.try_get_metadata("0x1::missing::MISSING").await.unwrap();
+        assert_eq!(fetcher.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_least_recently_used_entry_is_evicted() {
+        let (fetcher, client) = mock_client(CoinMetadataCacheConfig {
+            max_entries: Some(2),
+            ..Default::default()
+        });
+
+        client.get_metadata("0x1::a::A").await.unwrap();
+        client.get_metadata("0x1::b::B").await.unwrap();
+        client.get_metadata("0x1::a::A").await.unwrap();
+        client.get_metadata("0x1::c::C").await.unwrap();
+        assert_eq!(fetcher.calls.load(Ordering::SeqCst), 3);
+
+        // B was used least recently, so it was evicted and A was kept
+        client.get_metadata("0x1::a::A").await.unwrap();
+        assert_eq!(fetcher.calls.load(Ordering::SeqCst), 3);
+        client.get_metadata("0x1::b::B").await.unwrap();
+        assert_eq!(fetcher.calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_fetches_share_one_call() {
+        let (fetcher, client) = mock_client(CoinMetadataCacheConfig::default());
+
+        let results = futures::future::join_all((0..8).map(|_| client.get_metadata("0x1::a::A"))).await;
+        assert!(results.iter().all(|result| result.as_ref().is_ok_and(|metadata| metadata.symbol == "A")));
+        assert_eq!(fetcher.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_follower_takes_over_from_cancelled_leader() {
+        let (fetcher, client) = mock_client(CoinMetadataCacheConfig::default());
+
+        let mut leader = Box::pin(client.get_metadata("0x1::a::A"));
+        let mut follower = Box::pin(client.get_metadata("0x1::a::A"));
+        assert!(futures::poll!(leader.as_mut()).is_pending());
+        assert!(futures::poll!(follower.as_mut()).is_pending());
+        drop(leader);
+
+        // The follower still gets the result and fills the cache for later callers
+        assert_eq!(follower.await.unwrap().symbol, "A");
+        assert_eq!(client.get_metadata("0x1::a::A").await.unwrap().symbol, "A");
+        assert_eq!(fetcher.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_registry_entry_keeps_on_chain_logo() {
+        let (_, client) = mock_client(CoinMetadataCacheConfig::default());
+        let entry = |coin_type: &str, icon_url: Option<&str>| TokenEntry {
+            coin_type: coin_type.to_string(),
+            name: "Registry Token".to_string(),
+            symbol: "REG".to_string(),
+            decimals: 6,
+            description: String::new(),
+            icon_url: icon_url.map(str::to_string),
+            verified: true,
+        };
+        let registry = TokenRegistry::from_entries([entry("0x1::a::A", None), entry("0x1::b::B", Some("https://example.com/pinned.png"))]);
+        let client = client.with_token_registry(Arc::new(registry));
+
+        let a = client.get_metadata("0x1::a::A").await.unwrap();
+        assert_eq!((a.symbol.as_str(), a.decimals), ("REG", 6));
+        assert_eq!(a.icon_url.as_deref(), Some("https://example.com/0x1::a::A.png"));
+        let b = client.get_metadata("0x1::b::B").await.unwrap();
+        assert_eq!(b.icon_url.as_deref(), Some("https://example.com/pinned.png"));
+    }
+
+    struct FailingStore;
+
+    impl CoinMetadataStore for FailingStore {
+        fn load(&self) -> Result<HashMap<String, StoredMetadata>> {
+            Ok(HashMap::new())
+        }
+
+        fn save(&self, _coin_type: &str, _entry: &StoredMetadata) -> Result<()> {
+            Err(anyhow!("disk full"))
+        }
+
+        fn remove(&self, _coin_type: &str) -> Result<()> {
+            Err(anyhow!("disk full"))
+        }
+
+        fn clear(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_store_failure_does_not_fail_lookup() {
+        let (fetcher, client) = mock_client(CoinMetadataCacheConfig::default());
+        let client = client.with_store(Arc::new(FailingStore)).unwrap();
+
+        assert_eq!(client.get_metadata("0x1::a::A").await.unwrap().symbol, "A");
+        client.get_metadata("0x1::a::A").await.unwrap();
+        assert_eq!(fetcher.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_store_keeps_fetch_time_and_drops_missing_coins() {
+        let stored = |coin_type: &str, age: Duration| StoredMetadata {
+            metadata: SuiCoinMetadata {
+                decimals: 9,
+                name: coin_type.to_string(),
+                symbol: "OLD".to_string(),
+                description: String::new(),
+                icon_url: None,
+                id: None,
+            },
+            fetched_at: SystemTime::now() - age,
+            permanent: false,
+        };
+        let store = Arc::new(InMemoryMetadataStore::new());
+        store.save("0x1::a::A", &stored("0x1::a::A", Duration::from_secs(10))).unwrap();
+        store.save("0x1::b::B", &stored("0x1::b::B", Duration::from_secs(600))).unwrap();
+        store.save("0x1::missing::MISSING", &stored("0x1::missing::MISSING", Duration::ZERO)).unwrap();
+
+        let (fetcher, client) = mock_client(CoinMetadataCacheConfig {
+            ttl: Some(Duration::from_secs(60)),
+            ..Default::default()
+        });
+        let client = client.with_store(store.clone()).unwrap();
+
+        // A is still fresh, B expired while the process was not running
+        assert_eq!(client.get_metadata("0x1::a::A").await.unwrap().symbol, "OLD");
+        assert_eq!(client.get_metadata("0x1::b::B").await.unwrap().symbol, "B");
+        assert_eq!(fetcher.calls.load(Ordering::SeqCst), 1);
+
+        // A refresh finding no metadata removes the persisted entry
+        assert!(client.refresh_metadata("0x1::missing::MISSING").await.unwrap().is_none());
+        assert!(!store.load().unwrap().contains_key("0x1::missing::MISSING"));
     }
 }
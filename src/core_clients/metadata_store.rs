@@ -0,0 +1,293 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant, SystemTime};
+use sui_json_rpc_types::SuiCoinMetadata;
+
+/// A coin metadata entry as kept in a CoinMetadataStore
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredMetadata {
+    /// The metadata
+    pub metadata: SuiCoinMetadata,
+    /// When the metadata was fetched, so its TTL keeps running across restarts
+    pub fetched_at: SystemTime,
+    /// Whether the entry never expires, because its metadata object is frozen or it was set manually
+    #[serde(default)]
+    pub permanent: bool,
+}
+
+/// Trait for persisting coin metadata across process restarts
+///
+/// `CoinMetadataClient` loads all entries when it is created and writes every
+/// newly fetched entry through to the store. Calls are made from a blocking
+/// thread, so implementations may do blocking I/O.
+pub trait CoinMetadataStore: Send + Sync {
+    /// Load all stored entries
+    ///
+    /// # Returns
+    /// * `Result<HashMap<String, StoredMetadata>>` - Stored metadata keyed by coin type or an error
+    fn load(&self) -> Result<HashMap<String, StoredMetadata>>;
+
+    /// Add or update a single entry
+    ///
+    /// # Arguments
+    /// * `coin_type` - Coin type string, e.g. "0x2::sui::SUI"
+    /// * `entry` - The metadata to store
+    fn save(&self, coin_type: &str, entry: &StoredMetadata) -> Result<()>;
+
+    /// Remove a single entry, e.g. when the coin no longer has metadata
+    ///
+    /// # Arguments
+    /// * `coin_type` - Coin type string, e.g. "0x2::sui::SUI"
+    fn remove(&self, coin_type: &str) -> Result<()>;
+
+    /// Remove all entries
+    fn clear(&self) -> Result<()>;
+
+    /// Write out entries whose write was deferred, if the store batches writes
+    fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// CoinMetadataStore keeping entries in process memory only
+#[derive(Default)]
+pub struct InMemoryMetadataStore {
+    entries: Mutex<HashMap<String, StoredMetadata>>,
+}
+
+impl InMemoryMetadataStore {
+    /// Create a new empty InMemoryMetadataStore
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CoinMetadataStore for InMemoryMetadataStore {
+    fn load(&self) -> Result<HashMap<String, StoredMetadata>> {
+        Ok(self.entries.lock().unwrap().clone())
+    }
+
+    fn save(&self, coin_type: &str, entry: &StoredMetadata) -> Result<()> {
+        self.entries.lock().unwrap().insert(coin_type.to_string(), entry.clone());
+        Ok(())
+    }
+
+    fn remove(&self, coin_type: &str) -> Result<()> {
+        self.entries.lock().unwrap().remove(coin_type);
+        Ok(())
+    }
+
+    fn clear(&self) -> Result<()> {
+        self.entries.lock().unwrap().clear();
+        Ok(())
+    }
+}
+
+/// Minimum time between two writes of a `FileMetadataStore`
+pub const DEFAULT_FILE_WRITE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Entries of a FileMetadataStore and the state of their file
+struct FileState {
+    entries: HashMap<String, StoredMetadata>,
+    /// Whether entries changed since the last write
+    dirty: bool,
+    /// When the file was last written
+    last_write: Option<Instant>,
+}
+
+/// CoinMetadataStore persisting entries to a local JSON file
+///
+/// Saves are batched: the file is rewritten at most once per write interval, and
+/// deferred entries are written by `flush` or when the store is dropped. Writes go
+/// through a temporary file so that a crash never leaves a truncated store behind.
+pub struct FileMetadataStore {
+    /// Path of the JSON file
+    path: PathBuf,
+    /// Entries currently in the store
+    state: Mutex<FileState>,
+    /// Minimum time between two writes
+    write_interval: Duration,
+}
+
+impl FileMetadataStore {
+    /// Open a FileMetadataStore, reading the file if it exists
+    ///
+    /// # Arguments
+    /// * `path` - Path of the JSON file, created on first save
+    ///
+    /// # Returns
+    /// * `Result<FileMetadataStore>` - The store or an error if the file cannot be read
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let entries = if path.exists() {
+            serde_json::from_str(&fs::read_to_string(&path)?)?
+        } else {
+            HashMap::new()
+        };
+
+        Ok(Self {
+            path,
+            state: Mutex::new(FileState {
+                entries,
+                dirty: false,
+                last_write: None,
+            }),
+            write_interval: DEFAULT_FILE_WRITE_INTERVAL,
+        })
+    }
+
+    /// Set the minimum time between two writes, `Duration::ZERO` to write on every save
+    ///
+    /// # Arguments
+    /// * `write_interval` - The minimum time between two writes
+    ///
+    /// # Returns
+    /// * `FileMetadataStore` - The updated store
+    pub fn with_write_interval(mut self, write_interval: Duration) -> Self {
+        self.write_interval = write_interval;
+        self
+    }
+
+    /// Path of the JSON file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn lock(&self) -> MutexGuard<'_, FileState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Record a change, writing the file if the write interval has passed
+    fn mark_dirty(&self, state: &mut FileState) -> Result<()> {
+        state.dirty = true;
+        let due = state.last_write.map_or(true, |last_write| last_write.elapsed() >= self.write_interval);
+        if due {
+            self.write(state)?;
+        }
+        Ok(())
+    }
+
+    fn write(&self, state: &mut FileState) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let tmp_path = self.path.with_extension("tmp");
+        fs::write(&tmp_path, serde_json::to_vec(&state.entries)?)?;
+        fs::rename(&tmp_path, &self.path)?;
+        state.dirty = false;
+        state.last_write = Some(Instant::now());
+        Ok(())
+    }
+}
+
+impl CoinMetadataStore for FileMetadataStore {
+    fn load(&self) -> Result<HashMap<String, StoredMetadata>> {
+        Ok(self.lock().entries.clone())
+    }
+
+    fn save(&self, coin_type: &str, entry: &StoredMetadata) -> Result<()> {
+        let mut state = self.lock();
+        state.entries.insert(coin_type.to_string(), entry.clone());
+        self.mark_dirty(&mut state)
+    }
+
+    fn remove(&self, coin_type: &str) -> Result<()> {
+        let mut state = self.lock();
+        if state.entries.remove(coin_type).is_some() {
+            self.mark_dirty(&mut state)?;
+        }
+        Ok(())
+    }
+
+    fn clear(&self) -> Result<()> {
+        let mut state = self.lock();
+        state.entries.clear();
+        self.write(&mut state)
+    }
+
+    fn flush(&self) -> Result<()> {
+        let mut state = self.lock();
+        if state.dirty {
+            self.write(&mut state)?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for FileMetadataStore {
+    fn drop(&mut self) {
+        // Errors cannot be reported from drop; call `flush` to handle them
+        let _ = self.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usdc_metadata() -> StoredMetadata {
+        StoredMetadata {
+            metadata: SuiCoinMetadata {
+                decimals: 6,
+                name: "USD Coin".to_string(),
+                symbol: "USDC".to_string(),
+                description: "USDC is a US dollar-backed stablecoin".to_string(),
+                icon_url: None,
+                id: None,
+            },
+            fetched_at: SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+            permanent: false,
+        }
+    }
+
+    #[test]
+    fn test_file_store_survives_reopen() {
+        let path = std::env::temp_dir()
+            .join(format!("queryz_metadata_store_{}", std::process::id()))
+            .join("metadata.json");
+        let coin_type = "0xdba34672e30cb065b1f93e3ab55318768fd6fef66c15942c9f7cb846e2f900e7::usdc::USDC";
+
+        let store = FileMetadataStore::open(&path).unwrap();
+        store.save(coin_type, &usdc_metadata()).unwrap();
+        drop(store);
+
+        let reopened = FileMetadataStore::open(&path).unwrap();
+        let entries = reopened.load().unwrap();
+        let entry = entries.get(coin_type).unwrap();
+        assert_eq!(entry.metadata.symbol, "USDC");
+        assert_eq!(entry.fetched_at, usdc_metadata().fetched_at);
+
+        reopened.remove(coin_type).unwrap();
+        reopened.flush().unwrap();
+        assert!(FileMetadataStore::open(&path).unwrap().load().unwrap().is_empty());
+
+        reopened.save(coin_type, &usdc_metadata()).unwrap();
+        reopened.clear().unwrap();
+        assert!(FileMetadataStore::open(&path).unwrap().load().unwrap().is_empty());
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_file_store_batches_writes() {
+        let path = std::env::temp_dir()
+            .join(format!("queryz_metadata_store_batches_{}", std::process::id()))
+            .join("metadata.json");
+        let store = FileMetadataStore::open(&path).unwrap().with_write_interval(Duration::from_secs(3600));
+
+        // The first save writes, later ones wait for the interval or a flush
+        store.save("0x2::first::FIRST", &usdc_metadata()).unwrap();
+        store.save("0x2::second::SECOND", &usdc_metadata()).unwrap();
+        assert_eq!(FileMetadataStore::open(&path).unwrap().load().unwrap().len(), 1);
+
+        store.flush().unwrap();
+        assert_eq!(FileMetadataStore::open(&path).unwrap().load().unwrap().len(), 2);
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
@@ -1 +1,5 @@
 pub mod coin_metadata;
+pub mod metadata_store;
+pub mod move_layout;
+pub mod name_service;
+pub mod token_registry;
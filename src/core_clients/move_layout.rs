@@ -0,0 +1,202 @@
+use anyhow::{anyhow, Result};
+use futures::future::{BoxFuture, FutureExt};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::StructTag;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use sui_json_rpc_types::{SuiMoveNormalizedModule, SuiMoveNormalizedType};
+use sui_sdk::SuiClient;
+use sui_types::base_types::ObjectID;
+use sui_types::{TypeTag, MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS};
+use tokio::sync::RwLock;
+
+use crate::types::move_type::{check_layout, MoveType};
+use crate::types::move_value::{MoveStructLayout, MoveTypeLayout, MoveValue};
+
+/// Normalized modules of a package, using the module name as key
+type PackageModules = Arc<BTreeMap<String, SuiMoveNormalizedModule>>;
+
+/// MoveLayoutClient builds BCS layouts of Move types from on-chain normalized modules
+/// Modules are fetched once per package and cached
+pub struct MoveLayoutClient {
+    /// SUI client
+    client: Arc<SuiClient>,
+    /// Cache using the package ID as key
+    packages: RwLock<HashMap<ObjectID, PackageModules>>,
+}
+
+impl MoveLayoutClient {
+    /// Create a new MoveLayoutClient
+    ///
+    /// # Arguments
+    /// * `client` - SUI client used to fetch normalized modules
+    ///
+    /// # Returns
+    /// * `MoveLayoutClient` - A new client instance
+    pub fn new(client: Arc<SuiClient>) -> Self {
+        Self {
+            client,
+            packages: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Decode BCS bytes of a value of the given type
+    ///
+    /// # Arguments
+    /// * `type_tag` - The type of the value, e.g. "0x2::coin::Coin<0x2::sui::SUI>"
+    /// * `bytes` - The BCS bytes of the value
+    ///
+    /// # Returns
+    /// * `Result<MoveValue>` - The decoded value or an error
+    pub async fn decode(&self, type_tag: &TypeTag, bytes: &[u8]) -> Result<MoveValue> {
+        let layout = self.get_type_layout(type_tag).await?;
+        layout.decode(bytes)
+    }
+
+    /// Check that a Rust type matches the on-chain layout of its Move type
+    ///
+    /// Catches Rust mirrors whose fields are missing, misnamed or in the wrong order,
+    /// which would otherwise decode into garbage instead of failing.
+    ///
+    /// # Returns
+    /// * `Result<()>` - An error describing the first difference
+    pub async fn validate<T: MoveType>(&self) -> Result<()> {
+        let type_tag = T::type_tag();
+        let on_chain = self.get_type_layout(&type_tag).await?;
+        check_layout(&on_chain, &T::layout(), &type_tag.to_canonical_string(true))
+    }
+
+    /// Build the BCS layout of a type
+    ///
+    /// # Arguments
+    /// * `type_tag` - The type to build the layout of
+    ///
+    /// # Returns
+    /// * `Result<MoveTypeLayout>` - The layout or an error if a struct cannot be found
+    pub fn get_type_layout<'a>(&'a self, type_tag: &'a TypeTag) -> BoxFuture<'a, Result<MoveTypeLayout>> {
+        async move {
+            let layout = match type_tag {
+                TypeTag::Bool => MoveTypeLayout::Bool,
+                TypeTag::U8 => MoveTypeLayout::U8,
+                TypeTag::U16 => MoveTypeLayout::U16,
+                TypeTag::U32 => MoveTypeLayout::U32,
+                TypeTag::U64 => MoveTypeLayout::U64,
+                TypeTag::U128 => MoveTypeLayout::U128,
+                TypeTag::U256 => MoveTypeLayout::U256,
+                TypeTag::Address | TypeTag::Signer => MoveTypeLayout::Address,
+                TypeTag::Vector(inner) => MoveTypeLayout::Vector(Box::new(self.get_type_layout(inner).await?)),
+                TypeTag::Struct(tag) => match well_known_layout(tag) {
+                    Some(WellKnown::String) => MoveTypeLayout::String,
+                    Some(WellKnown::Id) => MoveTypeLayout::Id,
+                    Some(WellKnown::Option) => {
+                        let inner = tag.type_params
+                            .first()
+                            .ok_or_else(|| anyhow!("Option without type param"))?;
+                        MoveTypeLayout::Option(Box::new(self.get_type_layout(inner).await?))
+                    }
+                    None => MoveTypeLayout::Struct(self.get_struct_layout(tag).await?),
+                },
+            };
+            Ok(layout)
+        }
+        .boxed()
+    }
+
+    /// Build the BCS layout of a struct
+    ///
+    /// # Arguments
+    /// * `tag` - The struct type, with all type params
+    ///
+    /// # Returns
+    /// * `Result<MoveStructLayout>` - The layout or an error if the struct cannot be found
+    pub async fn get_struct_layout(&self, tag: &StructTag) -> Result<MoveStructLayout> {
+        let modules = self.get_package_modules(ObjectID::from(tag.address)).await?;
+        let normalized = modules
+            .get(tag.module.as_str())
+            .and_then(|module| module.structs.get(tag.name.as_str()))
+            .ok_or_else(|| anyhow!("Struct not found, type: {}", tag.to_canonical_string(true)))?;
+
+        let mut fields = Vec::with_capacity(normalized.fields.len());
+        for field in &normalized.fields {
+            let field_type = to_type_tag(&field.type_, &tag.type_params)?;
+            fields.push((field.name.clone(), self.get_type_layout(&field_type).await?));
+        }
+
+        Ok(MoveStructLayout {
+            type_: tag.clone(),
+            fields,
+        })
+    }
+
+    /// Get the normalized modules of a package, from cache or chain
+    async fn get_package_modules(&self, package_id: ObjectID) -> Result<PackageModules> {
+        {
+            let cache = self.packages.read().await;
+            if let Some(modules) = cache.get(&package_id) {
+                return Ok(modules.clone());
+            }
+        }
+
+        let modules = Arc::new(self.client.read_api().get_normalized_move_modules_by_package(package_id).await?);
+
+        let mut cache = self.packages.write().await;
+        cache.insert(package_id, modules.clone());
+        Ok(modules)
+    }
+
+    /// Clear the cache
+    pub async fn clear_cache(&self) {
+        self.packages.write().await.clear();
+    }
+}
+
+/// Framework types with a dedicated layout
+enum WellKnown {
+    String,
+    Id,
+    Option,
+}
+
+fn well_known_layout(tag: &StructTag) -> Option<WellKnown> {
+    match (tag.address, tag.module.as_str(), tag.name.as_str()) {
+        (MOVE_STDLIB_ADDRESS, "string", "String") | (MOVE_STDLIB_ADDRESS, "ascii", "String") => Some(WellKnown::String),
+        (MOVE_STDLIB_ADDRESS, "option", "Option") => Some(WellKnown::Option),
+        (SUI_FRAMEWORK_ADDRESS, "object", "UID") | (SUI_FRAMEWORK_ADDRESS, "object", "ID") => Some(WellKnown::Id),
+        _ => None,
+    }
+}
+
+/// Convert a normalized field type to a type tag, substituting the type params of the enclosing struct
+fn to_type_tag(normalized: &SuiMoveNormalizedType, type_params: &[TypeTag]) -> Result<TypeTag> {
+    let type_tag = match normalized {
+        SuiMoveNormalizedType::Bool => TypeTag::Bool,
+        SuiMoveNormalizedType::U8 => TypeTag::U8,
+        SuiMoveNormalizedType::U16 => TypeTag::U16,
+        SuiMoveNormalizedType::U32 => TypeTag::U32,
+        SuiMoveNormalizedType::U64 => TypeTag::U64,
+        SuiMoveNormalizedType::U128 => TypeTag::U128,
+        SuiMoveNormalizedType::U256 => TypeTag::U256,
+        SuiMoveNormalizedType::Address => TypeTag::Address,
+        SuiMoveNormalizedType::Signer => TypeTag::Signer,
+        SuiMoveNormalizedType::Vector(inner) => TypeTag::Vector(Box::new(to_type_tag(inner, type_params)?)),
+        SuiMoveNormalizedType::TypeParameter(index) => type_params
+            .get(*index as usize)
+            .cloned()
+            .ok_or_else(|| anyhow!("Type param {} out of range", index))?,
+        SuiMoveNormalizedType::Struct { inner } => TypeTag::Struct(Box::new(StructTag {
+            address: AccountAddress::from_hex_literal(&inner.address)?,
+            module: Identifier::new(inner.module.as_str())?,
+            name: Identifier::new(inner.name.as_str())?,
+            type_params: inner
+                .type_arguments
+                .iter()
+                .map(|argument| to_type_tag(argument, type_params))
+                .collect::<Result<_>>()?,
+        })),
+        SuiMoveNormalizedType::Reference(_) | SuiMoveNormalizedType::MutableReference(_) => {
+            return Err(anyhow!("References cannot be stored in objects"));
+        }
+    };
+    Ok(type_tag)
+}
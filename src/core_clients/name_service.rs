@@ -0,0 +1,342 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use sui_json_rpc_api::IndexerApiClient;
+use sui_sdk::SuiClient;
+use sui_types::base_types::SuiAddress;
+use tokio::sync::RwLock;
+
+/// Trait for resolving SuiNS names
+///
+/// Implemented over RPC by `RpcNameResolver`; tests can provide their own implementation.
+#[async_trait]
+pub trait NameResolver: Send + Sync {
+    /// Resolve a name (e.g. "example.sui") to the address it points to
+    ///
+    /// # Arguments
+    /// * `name` - The normalized name to resolve
+    ///
+    /// # Returns
+    /// * `Result<Option<SuiAddress>>` - The address, `None` if the name is not registered, or an error
+    async fn resolve_name(&self, name: &str) -> Result<Option<SuiAddress>>;
+
+    /// Look up the primary name of an address
+    ///
+    /// # Arguments
+    /// * `address` - The address to look up
+    ///
+    /// # Returns
+    /// * `Result<Option<String>>` - The primary name, `None` if the address has none, or an error
+    async fn primary_name(&self, address: SuiAddress) -> Result<Option<String>>;
+}
+
+/// NameResolver backed by the name service RPC methods of a fullnode
+pub struct RpcNameResolver {
+    /// SUI client
+    client: Arc<SuiClient>,
+}
+
+impl RpcNameResolver {
+    /// Create a new RpcNameResolver
+    ///
+    /// # Arguments
+    /// * `client` - SUI client used for the RPC calls
+    ///
+    /// # Returns
+    /// * `RpcNameResolver` - A new resolver instance
+    pub fn new(client: Arc<SuiClient>) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl NameResolver for RpcNameResolver {
+    async fn resolve_name(&self, name: &str) -> Result<Option<SuiAddress>> {
+        Ok(self.client.http().resolve_name_service_address(name.to_string()).await?)
+    }
+
+    async fn primary_name(&self, address: SuiAddress) -> Result<Option<String>> {
+        let names = self.client.http().resolve_name_service_names(address, None, Some(1)).await?;
+        Ok(names.data.into_iter().next())
+    }
+}
+
+/// How long a resolved name or primary name is cached
+pub const DEFAULT_NAME_TTL: Duration = Duration::from_secs(3600);
+/// How long an unregistered name or an address without primary name is cached
+pub const DEFAULT_NEGATIVE_NAME_TTL: Duration = Duration::from_secs(300);
+/// Maximum number of entries kept in each cache
+pub const DEFAULT_MAX_NAME_ENTRIES: usize = 10_000;
+
+/// A cached lookup result, `None` if nothing is registered
+struct CachedLookup<T> {
+    value: Option<T>,
+    fetched_at: Instant,
+}
+
+/// NameServiceClient resolves SuiNS names and caches the results
+/// Lookups are cached for `DEFAULT_NAME_TTL`, unregistered names for `DEFAULT_NEGATIVE_NAME_TTL`,
+/// and the oldest entries are evicted once a cache holds `DEFAULT_MAX_NAME_ENTRIES`
+pub struct NameServiceClient {
+    /// Resolver used on cache misses
+    resolver: Arc<dyn NameResolver>,
+    /// Cache using the normalized name as key
+    addresses: RwLock<HashMap<String, CachedLookup<SuiAddress>>>,
+    /// Cache of primary names using the address as key
+    primary_names: RwLock<HashMap<SuiAddress, CachedLookup<String>>>,
+    /// How long registered lookups are cached
+    ttl: Duration,
+    /// How long missing lookups are cached
+    negative_ttl: Duration,
+    /// Maximum number of entries of each cache
+    max_entries: usize,
+}
+
+impl NameServiceClient {
+    /// Create a new NameServiceClient
+    ///
+    /// # Arguments
+    /// * `resolver` - Resolver used on cache misses
+    ///
+    /// # Returns
+    /// * `NameServiceClient` - A new client instance
+    pub fn new(resolver: Arc<dyn NameResolver>) -> Self {
+        Self {
+            resolver,
+            addresses: RwLock::new(HashMap::new()),
+            primary_names: RwLock::new(HashMap::new()),
+            ttl: DEFAULT_NAME_TTL,
+            negative_ttl: DEFAULT_NEGATIVE_NAME_TTL,
+            max_entries: DEFAULT_MAX_NAME_ENTRIES,
+        }
+    }
+
+    /// Set how long registered and missing lookups are cached
+    ///
+    /// # Arguments
+    /// * `ttl` - How long registered names and primary names are cached
+    /// * `negative_ttl` - How long unregistered names and addresses without primary name are cached
+    ///
+    /// # Returns
+    /// * `NameServiceClient` - The updated client instance
+    pub fn with_ttl(mut self, ttl: Duration, negative_ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self.negative_ttl = negative_ttl;
+        self
+    }
+
+    /// Set the maximum number of entries of each cache
+    ///
+    /// # Arguments
+    /// * `max_entries` - The maximum number of entries, at least 1
+    ///
+    /// # Returns
+    /// * `NameServiceClient` - The updated client instance
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries.max(1);
+        self
+    }
+
+    /// Resolve a name to the address it points to
+    ///
+    /// # Arguments
+    /// * `name` - The name to resolve, e.g. "example.sui" or "@example"
+    ///
+    /// # Returns
+    /// * `Result<Option<SuiAddress>>` - The address, `None` if the name is not registered, or an error
+    pub async fn resolve_name(&self, name: &str) -> Result<Option<SuiAddress>> {
+        let name = normalize_name(name);
+        {
+            let cache = self.addresses.read().await;
+            if let Some(address) = self.cached(&cache, &name) {
+                return Ok(address);
+            }
+        }
+
+        let address = self.resolver.resolve_name(&name).await?;
+
+        let mut cache = self.addresses.write().await;
+        self.insert(&mut cache, name, address);
+        Ok(address)
+    }
+
+    /// Look up the primary name of an address
+    ///
+    /// # Arguments
+    /// * `address` - The address to look up
+    ///
+    /// # Returns
+    /// * `Result<Option<String>>` - The primary name, `None` if the address has none, or an error
+    pub async fn primary_name(&self, address: SuiAddress) -> Result<Option<String>> {
+        {
+            let cache = self.primary_names.read().await;
+            if let Some(name) = self.cached(&cache, &address) {
+                return Ok(name);
+            }
+        }
+
+        let name = self.resolver.primary_name(address).await?;
+
+        let mut cache = self.primary_names.write().await;
+        self.insert(&mut cache, address, name.clone());
+        Ok(name)
+    }
+
+    /// Parse an address, resolving it first if it is a name
+    ///
+    /// # Arguments
+    /// * `address_or_name` - A hex address or a name such as "example.sui"
+    ///
+    /// # Returns
+    /// * `Result<SuiAddress>` - The address or an error if the name is not registered
+    pub async fn resolve_address(&self, address_or_name: &str) -> Result<SuiAddress> {
+        if !is_sui_name(address_or_name) {
+            return Ok(SuiAddress::from_str(address_or_name)?);
+        }
+
+        self.resolve_name(address_or_name)
+            .await?
+            .ok_or_else(|| anyhow!("Name not found, name: {}", address_or_name))
+    }
+
+    /// Clear the cache
+    pub async fn clear_cache(&self) {
+        self.addresses.write().await.clear();
+        self.primary_names.write().await.clear();
+    }
+
+    /// Check whether a cached lookup is still fresh
+    fn is_fresh<T>(&self, entry: &CachedLookup<T>) -> bool {
+        let ttl = if entry.value.is_some() { self.ttl } else { self.negative_ttl };
+        entry.fetched_at.elapsed() < ttl
+    }
+
+    /// Get a fresh cached lookup, `None` on a miss
+    fn cached<K: Eq + Hash, T: Clone>(&self, cache: &HashMap<K, CachedLookup<T>>, key: &K) -> Option<Option<T>> {
+        cache.get(key).filter(|entry| self.is_fresh(entry)).map(|entry| entry.value.clone())
+    }
+
+    /// Cache a lookup, evicting stale entries and then the oldest one when the cache is full
+    fn insert<K: Eq + Hash + Clone, T>(&self, cache: &mut HashMap<K, CachedLookup<T>>, key: K, value: Option<T>) {
+        if cache.len() >= self.max_entries && !cache.contains_key(&key) {
+            cache.retain(|_, entry| self.is_fresh(entry));
+        }
+        if cache.len() >= self.max_entries && !cache.contains_key(&key) {
+            let oldest = cache.iter().min_by_key(|(_, entry)| entry.fetched_at).map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                cache.remove(&oldest);
+            }
+        }
+        cache.insert(key, CachedLookup { value, fetched_at: Instant::now() });
+    }
+}
+
+/// Check whether a string is a SuiNS name rather than a hex address
+pub fn is_sui_name(s: &str) -> bool {
+    let s = s.trim();
+    s.ends_with(".sui") || s.contains('@')
+}
+
+/// Normalize a name to the dot format, e.g. "sub@example" to "sub.example.sui"
+fn normalize_name(name: &str) -> String {
+    let name = name.trim().to_lowercase();
+    match name.split_once('@') {
+        Some((subdomain, domain)) => {
+            let mut normalized = if subdomain.is_empty() { String::new() } else { format!("{}.", subdomain) };
+            normalized.push_str(domain.trim_end_matches(".sui"));
+            normalized.push_str(".sui");
+            normalized
+        }
+        None => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct MockResolver {
+        calls: AtomicUsize,
+        address: SuiAddress,
+    }
+
+    #[async_trait]
+    impl NameResolver for MockResolver {
+        async fn resolve_name(&self, name: &str) -> Result<Option<SuiAddress>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok((name == "example.sui").then_some(self.address))
+        }
+
+        async fn primary_name(&self, address: SuiAddress) -> Result<Option<String>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok((address == self.address).then(|| "example.sui".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolution_is_cached() {
+        let resolver = Arc::new(MockResolver {
+            calls: AtomicUsize::new(0),
+            address: SuiAddress::random_for_testing_only(),
+        });
+        let client = NameServiceClient::new(resolver.clone());
+
+        assert_eq!(client.resolve_address("example.sui").await.unwrap(), resolver.address);
+        assert_eq!(client.resolve_address("@Example").await.unwrap(), resolver.address);
+        assert!(client.resolve_address("missing.sui").await.is_err());
+        assert!(client.resolve_address("missing.sui").await.is_err());
+        assert_eq!(resolver.calls.load(Ordering::SeqCst), 2);
+
+        assert_eq!(client.primary_name(resolver.address).await.unwrap().as_deref(), Some("example.sui"));
+        assert_eq!(client.primary_name(resolver.address).await.unwrap().as_deref(), Some("example.sui"));
+        assert_eq!(resolver.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_hex_address_is_not_resolved() {
+        let address = SuiAddress::random_for_testing_only();
+        let resolver = Arc::new(MockResolver {
+            calls: AtomicUsize::new(0),
+            address,
+        });
+        let client = NameServiceClient::new(resolver.clone());
+
+        assert_eq!(client.resolve_address(&address.to_string()).await.unwrap(), address);
+        assert_eq!(resolver.calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_cache_expires_and_is_bounded() {
+        let resolver = Arc::new(MockResolver {
+            calls: AtomicUsize::new(0),
+            address: SuiAddress::random_for_testing_only(),
+        });
+
+        // Missing names expire immediately, registered ones are kept
+        let client = NameServiceClient::new(resolver.clone()).with_ttl(DEFAULT_NAME_TTL, Duration::ZERO);
+        client.resolve_name("missing.sui").await.unwrap();
+        client.resolve_name("missing.sui").await.unwrap();
+        client.resolve_name("example.sui").await.unwrap();
+        client.resolve_name("example.sui").await.unwrap();
+        assert_eq!(resolver.calls.load(Ordering::SeqCst), 3);
+
+        let client = NameServiceClient::new(resolver.clone()).with_max_entries(1);
+        client.resolve_name("example.sui").await.unwrap();
+        client.resolve_name("missing.sui").await.unwrap();
+        assert_eq!(client.addresses.read().await.len(), 1);
+        client.resolve_name("example.sui").await.unwrap();
+        assert_eq!(resolver.calls.load(Ordering::SeqCst), 6);
+    }
+
+    #[test]
+    fn test_normalize_name() {
+        assert_eq!(normalize_name("Example.sui"), "example.sui");
+        assert_eq!(normalize_name("@example"), "example.sui");
+        assert_eq!(normalize_name("sub@example"), "sub.example.sui");
+    }
+}
@@ -0,0 +1,210 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use sui_json_rpc_types::SuiCoinMetadata;
+
+use crate::utils::coin::normalize_coin_type;
+
+/// Registry bundled with the crate, covering well-known mainnet tokens
+const BUNDLED_REGISTRY: &str = include_str!("token_registry.json");
+
+/// Metadata of a single token in a TokenRegistry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenEntry {
+    /// Coin type, e.g. "0x2::sui::SUI"
+    pub coin_type: String,
+    /// Name of the token
+    pub name: String,
+    /// Symbol of the token
+    pub symbol: String,
+    /// Number of decimals
+    pub decimals: u8,
+    /// Description of the token
+    #[serde(default)]
+    pub description: String,
+    /// URL of the token logo, `None` to keep the logo of the on-chain metadata
+    #[serde(default)]
+    pub icon_url: Option<String>,
+    /// Whether the entry has been checked against the token issuer
+    #[serde(default)]
+    pub verified: bool,
+}
+
+impl TokenEntry {
+    /// Convert the entry to the metadata format returned by the RPC
+    pub fn to_metadata(&self) -> SuiCoinMetadata {
+        SuiCoinMetadata {
+            decimals: self.decimals,
+            name: self.name.clone(),
+            symbol: self.symbol.clone(),
+            description: self.description.clone(),
+            icon_url: self.icon_url.clone(),
+            id: None,
+        }
+    }
+}
+
+/// Layout of a registry file
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RegistryFile {
+    #[serde(default)]
+    tokens: Vec<TokenEntry>,
+}
+
+/// TokenRegistry holds token metadata that takes precedence over on-chain `CoinMetadata`
+///
+/// On-chain metadata can be set to anything by the coin publisher, so a registry
+/// pins symbols, logos and decimals of known tokens. It can also provide metadata
+/// for test tokens, e.g. on localnet.
+#[derive(Debug, Clone, Default)]
+pub struct TokenRegistry {
+    /// Entries using the normalized coin type as key
+    entries: HashMap<String, TokenEntry>,
+}
+
+impl TokenRegistry {
+    /// Create an empty TokenRegistry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load the registry bundled with the crate
+    ///
+    /// # Returns
+    /// * `TokenRegistry` - The verified metadata of well-known mainnet tokens
+    pub fn bundled() -> Self {
+        Self::from_json_str(BUNDLED_REGISTRY).expect("bundled token registry is valid JSON")
+    }
+
+    /// Parse a registry from JSON
+    ///
+    /// # Arguments
+    /// * `s` - JSON of the form `{"tokens": [{"coin_type": ..., "symbol": ..., ...}]}`
+    ///
+    /// # Returns
+    /// * `Result<TokenRegistry>` - The registry or an error
+    pub fn from_json_str(s: &str) -> Result<Self> {
+        let file: RegistryFile = serde_json::from_str(s)?;
+        Ok(Self::from_entries(file.tokens))
+    }
+
+    /// Parse a registry from TOML
+    ///
+    /// # Arguments
+    /// * `s` - TOML with one `[[tokens]]` table per token
+    ///
+    /// # Returns
+    /// * `Result<TokenRegistry>` - The registry or an error
+    pub fn from_toml_str(s: &str) -> Result<Self> {
+        let file: RegistryFile = toml::from_str(s)?;
+        Ok(Self::from_entries(file.tokens))
+    }
+
+    /// Load a registry from a `.json` or `.toml` file
+    ///
+    /// # Arguments
+    /// * `path` - Path of the registry file
+    ///
+    /// # Returns
+    /// * `Result<TokenRegistry>` - The registry or an error
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)?;
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => Self::from_json_str(&content),
+            Some("toml") => Self::from_toml_str(&content),
+            _ => Err(anyhow!("Unsupported token registry format, path: {}", path.display())),
+        }
+    }
+
+    /// Create a registry from a list of entries
+    pub fn from_entries(entries: impl IntoIterator<Item = TokenEntry>) -> Self {
+        let mut registry = Self::new();
+        for entry in entries {
+            registry.insert(entry);
+        }
+        registry
+    }
+
+    /// Add or replace an entry
+    pub fn insert(&mut self, entry: TokenEntry) {
+        self.entries.insert(normalize_coin_type(&entry.coin_type), entry);
+    }
+
+    /// Add all entries of another registry, replacing entries for the same coin type
+    ///
+    /// # Arguments
+    /// * `other` - The registry whose entries take precedence
+    ///
+    /// # Returns
+    /// * `TokenRegistry` - The merged registry
+    pub fn merge(mut self, other: TokenRegistry) -> Self {
+        self.entries.extend(other.entries);
+        self
+    }
+
+    /// Get the entry of a coin type
+    ///
+    /// # Arguments
+    /// * `coin_type` - Coin type string, in short or full address form
+    ///
+    /// # Returns
+    /// * `Option<&TokenEntry>` - The entry, `None` if the coin type is not in the registry
+    pub fn get(&self, coin_type: &str) -> Option<&TokenEntry> {
+        self.entries.get(&normalize_coin_type(coin_type))
+    }
+
+    /// Number of entries in the registry
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check whether the registry has no entries
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_registry_matches_any_address_form() {
+        let registry = TokenRegistry::bundled();
+        let sui = registry
+            .get("0x0000000000000000000000000000000000000000000000000000000000000002::sui::SUI")
+            .unwrap();
+        assert_eq!(sui.symbol, "SUI");
+        assert_eq!(sui.decimals, 9);
+        assert!(sui.verified);
+    }
+
+    #[test]
+    fn test_user_registry_overrides_bundled() {
+        let user = TokenRegistry::from_toml_str(
+            r#"
+            [[tokens]]
+            coin_type = "0x2::sui::SUI"
+            name = "Localnet Sui"
+            symbol = "LSUI"
+            decimals = 9
+
+            [[tokens]]
+            coin_type = "0x42::test_coin::TEST_COIN"
+            name = "Test Coin"
+            symbol = "TEST"
+            decimals = 6
+            "#,
+        )
+        .unwrap();
+        let registry = TokenRegistry::bundled().merge(user);
+
+        assert_eq!(registry.get("0x2::sui::SUI").unwrap().symbol, "LSUI");
+        let test_coin = registry.get("0x42::test_coin::TEST_COIN").unwrap();
+        assert_eq!(test_coin.decimals, 6);
+        assert!(!test_coin.verified);
+    }
+}
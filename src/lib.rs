@@ -1,16 +1,23 @@
 // Queryz: A utility library for Sui blockchain
 // This library provides a set of tools to query complex objects on the Sui blockchain
 
+// Lets code generated by queryz-derive refer to `::queryz` inside this crate too
+extern crate self as queryz;
+
 pub mod sdks;
 pub mod client;
 pub mod query;
 pub mod types;
 pub mod utils;
 pub mod core_clients;
+pub mod codegen;
 
 // Re-export commonly used items
 pub use client::SuiQueryZClient;
 pub use query::bag::BagQuery;
 pub use query::wallet::WalletQuery;
+pub use query::kiosk::KioskQuery;
+pub use query::coin::CoinQuery;
+pub use query::deny_list::DenyListQuery;
 pub use sdks::publish_contract::*;
 
@@ -5,14 +5,28 @@ use sui_types::coin::Coin;
 use sui_types::dynamic_field::Field;
 use sui_types::base_types::ObjectID;
 use std::sync::Arc;
+use move_core_types::language_storage::StructTag;
 use sui_json_rpc_types::{SuiData, SuiObjectDataOptions};
+use sui_types::TypeTag;
 use crate::client::SuiQueryZClient;
-use crate::types::bag::{Balancez, BagBalances};
+use crate::query::metadata_policy::MissingMetadataPolicy;
+use crate::types::bag::{BagBalances, BagEntry, Balancez};
+use crate::types::move_type::MoveType;
+use crate::types::move_value::MoveValue;
 use crate::types::type_name::TypeName;
+
+/// A dynamic field object of a Bag
+struct BagFieldObject {
+    object_id: ObjectID,
+    type_: StructTag,
+    bcs_bytes: Vec<u8>,
+}
+
 /// BagQuery provides methods to query Sui Bag objects
 #[derive(Clone)]
 pub struct BagQuery {
     client: Arc<SuiQueryZClient>,
+    missing_metadata_policy: MissingMetadataPolicy,
 }
 
 impl BagQuery {
@@ -24,7 +38,22 @@ impl BagQuery {
     /// # Returns
     /// * `BagQuery` - A new BagQuery instance
     pub fn new(client: Arc<SuiQueryZClient>) -> Self {
-        Self { client }
+        Self {
+            client,
+            missing_metadata_policy: MissingMetadataPolicy::default(),
+        }
+    }
+
+    /// Set how balance queries treat coins without `CoinMetadata`
+    ///
+    /// # Arguments
+    /// * `policy` - The policy to apply, `MissingMetadataPolicy::Fail` by default
+    ///
+    /// # Returns
+    /// * `BagQuery` - The updated BagQuery instance
+    pub fn with_missing_metadata_policy(mut self, policy: MissingMetadataPolicy) -> Self {
+        self.missing_metadata_policy = policy;
+        self
     }
 
     /// Get all balances in a Bag object
@@ -36,6 +65,69 @@ impl BagQuery {
     /// * `Result<BagBalances>` - The balances in the Bag or an error
     pub async fn get_bag_raw_fields<T: DeserializeOwned>(&self, bag_id: ObjectID) -> Result<Vec<T>> {
         let mut fields: Vec<T> = vec![];
+        for object in self.get_bag_field_objects(bag_id).await? {
+            fields.push(bcs::from_bytes(object.bcs_bytes.as_ref())?);
+        }
+        Ok(fields)
+    }
+
+    /// Get all entries of a Bag object whose keys and values all have the same types
+    ///
+    /// The Rust types are checked against the on-chain layout first, so a mismatched
+    /// mirror fails with a clear error instead of decoding garbage.
+    ///
+    /// # Arguments
+    /// * `bag_id` - The ID of the Bag object to query
+    ///
+    /// # Returns
+    /// * `Result<Vec<Field<N, V>>>` - The entries or an error
+    pub async fn get_bag_typed_fields<N, V>(&self, bag_id: ObjectID) -> Result<Vec<Field<N, V>>>
+    where
+        N: MoveType + DeserializeOwned,
+        V: MoveType + DeserializeOwned,
+    {
+        self.client.move_layout_client().validate::<Field<N, V>>().await?;
+        self.get_bag_raw_fields(bag_id).await
+    }
+
+    /// Get all entries of a Bag object, decoded from the on-chain layout of their types
+    ///
+    /// No Rust types are needed, so Bags and Tables holding unknown types can be inspected.
+    ///
+    /// # Arguments
+    /// * `bag_id` - The ID of the Bag object to query
+    ///
+    /// # Returns
+    /// * `Result<Vec<BagEntry>>` - The decoded entries or an error
+    pub async fn get_bag_entries(&self, bag_id: ObjectID) -> Result<Vec<BagEntry>> {
+        let layout_client = self.client.move_layout_client();
+        let mut entries = vec![];
+
+        for object in self.get_bag_field_objects(bag_id).await? {
+            let field_type = TypeTag::Struct(Box::new(object.type_.clone()));
+            let MoveValue::Struct(mut field) = layout_client.decode(&field_type, &object.bcs_bytes).await? else {
+                return Err(anyhow!("dynamic field is not a struct"));
+            };
+
+            // Fields are laid out as `Field { id, name, value }`
+            let value = field.fields.pop().map(|(_, value)| value);
+            let name = field.fields.pop().map(|(_, value)| value);
+            let (Some(name), Some(value)) = (name, value) else {
+                return Err(anyhow!("dynamic field has unexpected layout, type: {}", object.type_));
+            };
+            entries.push(BagEntry {
+                field_id: object.object_id,
+                name,
+                value,
+            });
+        }
+
+        Ok(entries)
+    }
+
+    /// Fetch the dynamic field objects of a Bag with their BCS contents
+    async fn get_bag_field_objects(&self, bag_id: ObjectID) -> Result<Vec<BagFieldObject>> {
+        let mut objects = vec![];
         let mut cursor: Option<ObjectID> = None;
     
         let limit = 50;
@@ -54,30 +146,28 @@ impl BagQuery {
                 .multi_get_object_with_options(field_ids.clone(), SuiObjectDataOptions::bcs_lossless())
                 .await?;
     
-            for i in 0..field_ids.len() {
-                let item = &resp[i];
-                if item.data.is_none() {
+            for item in resp {
+                let Some(data) = item.data else {
                     continue;
-                }
-                let data = item.data.clone().ok_or(anyhow!("object data is none"))?;
-                let field: T = bcs::from_bytes(
-                    data.clone()
-                        .bcs
-                        .ok_or(anyhow!("object data bcs is none"))?
-                        .try_as_move()
-                        .ok_or(anyhow!("object data bcs is not move"))?
-                        .bcs_bytes
-                        .as_ref(),
-                )?;
-                fields.push(field);
+                };
+                let move_object = data
+                    .bcs
+                    .ok_or(anyhow!("object data bcs is none"))?
+                    .try_into_move()
+                    .ok_or(anyhow!("object data bcs is not move"))?;
+                objects.push(BagFieldObject {
+                    object_id: data.object_id,
+                    type_: move_object.type_,
+                    bcs_bytes: move_object.bcs_bytes,
+                });
             }
     
             if !fields_resp.has_next_page {
                 break;
             }
         }
-        Ok(fields)
-    }   
+        Ok(objects)
+    }
 
     /// Convert raw field data to balance information
     ///
@@ -89,17 +179,20 @@ impl BagQuery {
     /// * `BagBalances` - Processed balance information
     async fn process_bag_balances(&self, bag_id: ObjectID, fields: Vec<Field<TypeName, Coin>>) -> Result<BagBalances> {
         let mut balances: Vec<Balancez> = vec![];
+        let coin_metadata_client = self.client.coin_metadata_client();
+
+        // Fetch all metadata concurrently up front, so the loop below reads from the cache
+        let coin_types: Vec<String> = fields.iter().map(|field| field.name.to_string()).collect();
+        coin_metadata_client.preload(&coin_types).await;
 
         for field in fields {
-            let metadata = self.client.get_coin_metadata(&field.name.to_string()).await?;
+            let coin_type = field.name.to_string();
+            let metadata = self.client.try_get_coin_metadata(&coin_type).await?;
+            let token_entry = coin_metadata_client.token_entry(&coin_type).cloned();
 
-            let actual_balance = field.value.value() as f64 / 10.0_f64.powi(metadata.decimals as i32);
-            let balance = Balancez {
-                symbol: metadata.symbol,
-                coin_type: field.name.to_string(),
-                balance: actual_balance,
-            };
-            balances.push(balance);
+            if let Some(balance) = self.missing_metadata_policy.balance_entry(coin_type, field.value.value() as u128, metadata.as_ref())? {
+                balances.push(balance.with_token_entry(token_entry.as_ref()));
+            }
         }
         
         Ok(BagBalances { bag_id, balances })
@@ -129,6 +222,30 @@ pub trait BagQuerier {
     /// # Returns
     /// * `Result<BagBalances>` - The balances in the Bag or an error
     async fn get_bag_balances(&self, bag_id: ObjectID) -> Result<BagBalances>;
+
+    /// Get all entries of a Bag object whose keys and values all have the same types
+    ///
+    /// The Rust types are checked against the on-chain layout first, so a mismatched
+    /// mirror fails with a clear error instead of decoding garbage.
+    ///
+    /// # Arguments
+    /// * `bag_id` - The ID of the Bag object to query
+    ///
+    /// # Returns
+    /// * `Result<Vec<Field<N, V>>>` - The entries or an error
+    async fn get_bag_typed_fields<N, V>(&self, bag_id: ObjectID) -> Result<Vec<Field<N, V>>>
+    where
+        N: MoveType + DeserializeOwned + Send + 'static,
+        V: MoveType + DeserializeOwned + Send + 'static;
+
+    /// Get all entries of a Bag object, decoded from the on-chain layout of their types
+    ///
+    /// # Arguments
+    /// * `bag_id` - The ID of the Bag object to query
+    ///
+    /// # Returns
+    /// * `Result<Vec<BagEntry>>` - The decoded entries or an error
+    async fn get_bag_entries(&self, bag_id: ObjectID) -> Result<Vec<BagEntry>>;
 }
 
 #[async_trait]
@@ -137,4 +254,18 @@ impl BagQuerier for SuiQueryZClient {
         let query = BagQuery::new(Arc::new(self.clone()));
         query.get_bag_balances(bag_id).await
     }
+
+    async fn get_bag_typed_fields<N, V>(&self, bag_id: ObjectID) -> Result<Vec<Field<N, V>>>
+    where
+        N: MoveType + DeserializeOwned + Send + 'static,
+        V: MoveType + DeserializeOwned + Send + 'static,
+    {
+        let query = BagQuery::new(Arc::new(self.clone()));
+        query.get_bag_typed_fields(bag_id).await
+    }
+
+    async fn get_bag_entries(&self, bag_id: ObjectID) -> Result<Vec<BagEntry>> {
+        let query = BagQuery::new(Arc::new(self.clone()));
+        query.get_bag_entries(bag_id).await
+    }
 } 
@@ -0,0 +1,221 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use std::sync::Arc;
+use move_core_types::language_storage::StructTag;
+use sui_json_rpc_types::{ObjectChange, SuiObjectDataOptions, SuiRawData, SuiTransactionBlockResponseOptions, SuiTransactionBlockResponseQuery, TransactionFilter};
+use sui_types::base_types::ObjectID;
+use sui_types::coin::TreasuryCap;
+use sui_types::digests::TransactionDigest;
+use sui_types::error::SuiObjectResponseError;
+use sui_types::object::Owner;
+use sui_types::{parse_sui_struct_tag, TypeTag, SUI_FRAMEWORK_ADDRESS};
+
+use crate::client::SuiQueryZClient;
+use crate::types::coin::{CapInfo, CapOwnership, CoinInfo};
+
+/// Objects of the `0x2::coin` module created for a coin when its package was published
+#[derive(Default)]
+struct CurrencyObjects {
+    treasury_cap: Option<(ObjectID, StructTag)>,
+    deny_cap: Option<(ObjectID, StructTag)>,
+    regulated_metadata: bool,
+}
+
+/// CoinQuery provides methods to inspect coin types before listing them
+#[derive(Clone)]
+pub struct CoinQuery {
+    client: Arc<SuiQueryZClient>,
+}
+
+impl CoinQuery {
+    /// Create a new CoinQuery instance
+    ///
+    /// # Arguments
+    /// * `client` - The SuiClient to use for queries
+    ///
+    /// # Returns
+    /// * `CoinQuery` - A new CoinQuery instance
+    pub fn new(client: Arc<SuiQueryZClient>) -> Self {
+        Self { client }
+    }
+
+    /// Get metadata, total supply, treasury and regulation information of a coin type
+    ///
+    /// The metadata is always read from chain, never from a token registry. The `TreasuryCap` and
+    /// `DenyCap` are looked up in the transactions that created the coin's `CoinMetadata` and
+    /// published its package, which is where `coin::create_currency` creates them. When neither
+    /// transaction can be found, e.g. for system packages, the caps and the total supply are `None`.
+    ///
+    /// # Arguments
+    /// * `coin_type` - The coin type, e.g. "0x2::sui::SUI"
+    ///
+    /// # Returns
+    /// * `Result<CoinInfo>` - The coin information or an error
+    pub async fn get_coin_info(&self, coin_type: &str) -> Result<CoinInfo> {
+        let coin_tag = parse_sui_struct_tag(coin_type)?;
+        let metadata = self.client
+            .sui_client()
+            .coin_read_api()
+            .get_coin_metadata(coin_type.to_string())
+            .await?;
+        let metadata_id = metadata.as_ref().and_then(|metadata| metadata.id);
+        let objects = self.get_currency_objects(&coin_tag, metadata_id).await?;
+
+        let treasury_cap = match objects.treasury_cap {
+            Some((object_id, object_type)) => Some(self.get_cap_info(object_id, object_type).await?),
+            None => None,
+        };
+        let deny_cap = match objects.deny_cap {
+            Some((object_id, object_type)) => Some(self.get_cap_info(object_id, object_type).await?),
+            None => None,
+        };
+
+        // The supply lives in the TreasuryCap, which cannot be read once it is wrapped or deleted
+        let total_supply = match &treasury_cap {
+            Some(cap) if !matches!(cap.ownership, CapOwnership::Wrapped | CapOwnership::Burned) => {
+                self.get_total_supply(cap.object_id).await?
+            }
+            _ => None,
+        };
+
+        Ok(CoinInfo {
+            coin_type: coin_type.to_string(),
+            metadata,
+            total_supply,
+            treasury_cap,
+            regulated: deny_cap.is_some() || objects.regulated_metadata,
+            deny_cap,
+        })
+    }
+
+    /// Find the currency objects created by the transactions that created the coin's metadata or published its package
+    async fn get_currency_objects(&self, coin_tag: &StructTag, metadata_id: Option<ObjectID>) -> Result<CurrencyObjects> {
+        let mut digests = vec![];
+        if let Some(metadata_id) = metadata_id {
+            if let Some(digest) = self.get_creating_transaction(metadata_id).await? {
+                digests.push(digest);
+            }
+        }
+        // Packages are immutable, so their previous transaction is the publish; a missing package leaves the caps unknown
+        if let Some(digest) = self.get_previous_transaction(ObjectID::from(coin_tag.address)).await? {
+            if !digests.contains(&digest) {
+                digests.push(digest);
+            }
+        }
+
+        let mut objects = CurrencyObjects::default();
+        for digest in digests {
+            let transaction = self.client
+                .get_transaction_with_options(digest, SuiTransactionBlockResponseOptions::new().with_object_changes())
+                .await?;
+
+            for change in transaction.object_changes.unwrap_or_default() {
+                let ObjectChange::Created { object_type, object_id, .. } = change else {
+                    continue;
+                };
+                if !is_currency_object_of(&object_type, coin_tag) {
+                    continue;
+                }
+
+                match object_type.name.as_str() {
+                    "TreasuryCap" => objects.treasury_cap = Some((object_id, object_type)),
+                    "DenyCap" | "DenyCapV2" => objects.deny_cap = Some((object_id, object_type)),
+                    "RegulatedCoinMetadata" => objects.regulated_metadata = true,
+                    _ => {}
+                }
+            }
+            if objects.treasury_cap.is_some() {
+                break;
+            }
+        }
+
+        Ok(objects)
+    }
+
+    /// Get the digest of the first transaction that changed an object, i.e. the one that created it
+    async fn get_creating_transaction(&self, object_id: ObjectID) -> Result<Option<TransactionDigest>> {
+        let query = SuiTransactionBlockResponseQuery::new(Some(TransactionFilter::ChangedObject(object_id)), None);
+        let page = self.client.query_transaction_blocks(query, None, Some(1), false).await?;
+        Ok(page.data.first().map(|transaction| transaction.digest))
+    }
+
+    /// Read the total supply from a TreasuryCap
+    ///
+    /// # Returns
+    /// * `Result<Option<u64>>` - The supply, `None` if the cap object no longer exists, or an error
+    async fn get_total_supply(&self, treasury_cap_id: ObjectID) -> Result<Option<u64>> {
+        let resp = self.client
+            .get_object_with_options(treasury_cap_id, SuiObjectDataOptions::new().with_bcs())
+            .await?;
+        match resp.data.and_then(|data| data.bcs) {
+            Some(SuiRawData::MoveObject(object)) => Ok(Some(TreasuryCap::from_bcs_bytes(&object.bcs_bytes)?.total_supply.value)),
+            Some(SuiRawData::Package(_)) => Err(anyhow!("Expected a TreasuryCap, got a package, object_id: {}", treasury_cap_id)),
+            None => Ok(None),
+        }
+    }
+
+    /// Get the digest of the transaction that last modified an object
+    async fn get_previous_transaction(&self, object_id: ObjectID) -> Result<Option<TransactionDigest>> {
+        let resp = self.client
+            .get_object_with_options(object_id, SuiObjectDataOptions::new().with_previous_transaction())
+            .await?;
+        Ok(resp.data.and_then(|data| data.previous_transaction))
+    }
+
+    /// Look up who currently holds a capability object
+    async fn get_cap_info(&self, object_id: ObjectID, object_type: StructTag) -> Result<CapInfo> {
+        let resp = self.client
+            .get_object_with_options(object_id, SuiObjectDataOptions::new().with_owner())
+            .await?;
+
+        let ownership = match (resp.data.and_then(|data| data.owner), resp.error) {
+            (Some(owner), _) => match owner {
+                Owner::AddressOwner(address) => CapOwnership::AddressOwned(address),
+                Owner::ObjectOwner(address) => CapOwnership::ObjectOwned(address),
+                Owner::Shared { .. } => CapOwnership::Shared,
+                Owner::Immutable => CapOwnership::Frozen,
+                // Consensus-ordered objects are still owned by a single address
+                owner => CapOwnership::AddressOwned(owner.get_owner_address()?),
+            },
+            (None, Some(SuiObjectResponseError::Deleted { digest, .. })) if digest.is_wrapped() => CapOwnership::Wrapped,
+            (None, Some(SuiObjectResponseError::Deleted { .. })) => CapOwnership::Burned,
+            (None, error) => {
+                return Err(anyhow!("Failed to get capability object, object_id: {}, error: {:?}", object_id, error));
+            }
+        };
+
+        Ok(CapInfo {
+            object_id,
+            object_type: object_type.to_canonical_string(true),
+            ownership,
+        })
+    }
+}
+
+/// Check whether a type is a struct of the `0x2::coin` module instantiated with the given coin
+fn is_currency_object_of(object_type: &StructTag, coin_tag: &StructTag) -> bool {
+    object_type.address == SUI_FRAMEWORK_ADDRESS
+        && object_type.module.as_str() == "coin"
+        && matches!(object_type.type_params.as_slice(), [TypeTag::Struct(tag)] if tag.as_ref() == coin_tag)
+}
+
+/// Trait for querying coin types
+#[async_trait]
+pub trait CoinQuerier {
+    /// Get metadata, total supply, treasury and regulation information of a coin type
+    ///
+    /// # Arguments
+    /// * `coin_type` - The coin type, e.g. "0x2::sui::SUI"
+    ///
+    /// # Returns
+    /// * `Result<CoinInfo>` - The coin information or an error
+    async fn get_coin_info(&self, coin_type: &str) -> Result<CoinInfo>;
+}
+
+#[async_trait]
+impl CoinQuerier for SuiQueryZClient {
+    async fn get_coin_info(&self, coin_type: &str) -> Result<CoinInfo> {
+        let query = CoinQuery::new(Arc::new(self.clone()));
+        query.get_coin_info(coin_type).await
+    }
+}
@@ -0,0 +1,289 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::Arc;
+use sui_json_rpc_types::SuiObjectDataOptions;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::dynamic_field::{derive_dynamic_field_id, Field};
+use sui_types::id::ID;
+use sui_types::{parse_sui_type_tag, TypeTag, SUI_DENY_LIST_OBJECT_ID};
+
+use crate::client::SuiQueryZClient;
+use crate::types::deny_list::{AddressDenyStatus, CoinDenyStatus};
+use crate::utils::object::parse_move_object_bcs;
+
+/// Maximum number of objects requested per batch
+const OBJECTS_PAGE_LIMIT: usize = 50;
+
+/// Index of the coin deny lists in the `DenyList` object
+const COIN_DENY_LIST_INDEX: u64 = 0;
+
+/// Mirror of `0x2::deny_list::ConfigKey`
+#[derive(Serialize, Deserialize)]
+struct ConfigKey {
+    per_type_index: u64,
+    per_type_key: Vec<u8>,
+}
+
+/// Mirror of `0x2::deny_list::AddressKey`
+#[derive(Serialize, Deserialize)]
+struct AddressKey(SuiAddress);
+
+/// Mirror of `0x2::deny_list::GlobalPauseKey`
+#[derive(Serialize, Deserialize)]
+struct GlobalPauseKey {
+    dummy_field: bool,
+}
+
+/// Mirror of `0x2::config::Setting<bool>`
+#[derive(Deserialize)]
+struct Setting {
+    data: Option<SettingData>,
+}
+
+/// Mirror of `0x2::config::SettingData<bool>`
+#[derive(Deserialize)]
+struct SettingData {
+    newer_value_epoch: u64,
+    newer_value: Option<bool>,
+    older_value_opt: Option<bool>,
+}
+
+impl Setting {
+    /// The value in effect in the given epoch
+    ///
+    /// The newer value only takes effect in the epoch after it was written.
+    fn current_value(&self, epoch: u64) -> bool {
+        self.data
+            .as_ref()
+            .and_then(|data| if epoch > data.newer_value_epoch { data.newer_value } else { data.older_value_opt })
+            .unwrap_or(false)
+    }
+
+    /// The value that will be in effect in the next epoch
+    fn next_value(&self) -> bool {
+        self.data.as_ref().and_then(|data| data.newer_value).unwrap_or(false)
+    }
+}
+
+/// DenyListQuery provides methods to check the deny lists of regulated coins
+///
+/// Only coins regulated through `DenyCapV2` are supported; their deny lists are
+/// stored as configs under the `DenyList` system object at `0x403`.
+#[derive(Clone)]
+pub struct DenyListQuery {
+    client: Arc<SuiQueryZClient>,
+}
+
+impl DenyListQuery {
+    /// Create a new DenyListQuery instance
+    ///
+    /// # Arguments
+    /// * `client` - The SuiClient to use for queries
+    ///
+    /// # Returns
+    /// * `DenyListQuery` - A new DenyListQuery instance
+    pub fn new(client: Arc<SuiQueryZClient>) -> Self {
+        Self { client }
+    }
+
+    /// Check whether addresses are on the deny list of a coin, for the current and next epoch
+    ///
+    /// # Arguments
+    /// * `coin_type` - The coin type, e.g. "0x2::sui::SUI"
+    /// * `addresses` - The addresses or SuiNS names to check
+    ///
+    /// # Returns
+    /// * `Result<CoinDenyStatus>` - The deny list state of the coin or an error
+    pub async fn get_deny_status(&self, coin_type: &str, addresses: &[String]) -> Result<CoinDenyStatus> {
+        let mut resolved = Vec::with_capacity(addresses.len());
+        for address in addresses {
+            resolved.push(self.client.resolve_address(address).await?);
+        }
+
+        let epoch = self.client.get_current_epoch().await?;
+        let config_ids = self.get_config_ids(&[coin_type.to_string()]).await?;
+
+        let mut status = CoinDenyStatus {
+            coin_type: coin_type.to_string(),
+            epoch,
+            regulated: false,
+            paused_current_epoch: false,
+            paused_next_epoch: false,
+            addresses: resolved
+                .iter()
+                .map(|address| AddressDenyStatus {
+                    address: *address,
+                    denied_current_epoch: false,
+                    denied_next_epoch: false,
+                })
+                .collect(),
+        };
+        let Some(config_id) = config_ids.get(coin_type).copied() else {
+            return Ok(status);
+        };
+        status.regulated = true;
+
+        let pause = self.get_global_pause_settings(&[config_id]).await?;
+        if let Some(setting) = pause.get(&config_id) {
+            status.paused_current_epoch = setting.current_value(epoch);
+            status.paused_next_epoch = setting.next_value();
+        }
+
+        let keys = resolved.iter().map(|address| (config_id, *address)).collect();
+        let denied = self.get_address_settings(keys).await?;
+        for address_status in &mut status.addresses {
+            if let Some(setting) = denied.get(&(config_id, address_status.address)) {
+                address_status.denied_current_epoch = setting.current_value(epoch);
+                address_status.denied_next_epoch = setting.next_value();
+            }
+        }
+
+        Ok(status)
+    }
+
+    /// Find the coin types an address cannot use in the current epoch
+    ///
+    /// # Arguments
+    /// * `address` - The address to check
+    /// * `coin_types` - The coin types to check
+    ///
+    /// # Returns
+    /// * `Result<HashSet<String>>` - The coin types that are paused or deny the address, or an error
+    pub async fn get_frozen_coin_types(&self, address: SuiAddress, coin_types: &[String]) -> Result<HashSet<String>> {
+        let config_ids = self.get_config_ids(coin_types).await?;
+        if config_ids.is_empty() {
+            return Ok(HashSet::new());
+        }
+
+        let epoch = self.client.get_current_epoch().await?;
+        let configs: Vec<ObjectID> = config_ids.values().copied().collect();
+        let pause = self.get_global_pause_settings(&configs).await?;
+        let denied = self
+            .get_address_settings(configs.iter().map(|config_id| (*config_id, address)).collect())
+            .await?;
+
+        let frozen = config_ids
+            .into_iter()
+            .filter(|(_, config_id)| {
+                pause.get(config_id).is_some_and(|setting| setting.current_value(epoch))
+                    || denied.get(&(*config_id, address)).is_some_and(|setting| setting.current_value(epoch))
+            })
+            .map(|(coin_type, _)| coin_type)
+            .collect();
+        Ok(frozen)
+    }
+
+    /// Find the deny list configs of coin types, skipping coins that are not regulated
+    async fn get_config_ids(&self, coin_types: &[String]) -> Result<HashMap<String, ObjectID>> {
+        // Configs are dynamic object fields of the DenyList, keyed by the coin type without "0x"
+        let key_type = parse_sui_type_tag("0x2::dynamic_object_field::Wrapper<0x2::deny_list::ConfigKey>")?;
+        let mut field_ids = HashMap::new();
+        for coin_type in coin_types {
+            let key = ConfigKey {
+                per_type_index: COIN_DENY_LIST_INDEX,
+                per_type_key: TypeTag::from_str(coin_type)?.to_canonical_string(false).into_bytes(),
+            };
+            let field_id = derive_dynamic_field_id(SUI_DENY_LIST_OBJECT_ID, &key_type, &bcs::to_bytes(&key)?)?;
+            field_ids.insert(field_id, coin_type.clone());
+        }
+
+        let fields: HashMap<ObjectID, Field<ConfigKey, ID>> = self.get_fields(field_ids.keys().copied().collect()).await?;
+        Ok(fields
+            .into_iter()
+            .filter_map(|(field_id, field)| Some((field_ids.remove(&field_id)?, field.value.bytes)))
+            .collect())
+    }
+
+    /// Get the global pause settings of deny list configs
+    async fn get_global_pause_settings(&self, config_ids: &[ObjectID]) -> Result<HashMap<ObjectID, Setting>> {
+        let key_type = parse_sui_type_tag("0x2::deny_list::GlobalPauseKey")?;
+        let key_bytes = bcs::to_bytes(&GlobalPauseKey { dummy_field: false })?;
+        let mut field_ids = HashMap::new();
+        for config_id in config_ids {
+            field_ids.insert(derive_dynamic_field_id(*config_id, &key_type, &key_bytes)?, *config_id);
+        }
+
+        let fields: HashMap<ObjectID, Field<GlobalPauseKey, Setting>> = self.get_fields(field_ids.keys().copied().collect()).await?;
+        Ok(fields
+            .into_iter()
+            .filter_map(|(field_id, field)| Some((field_ids.remove(&field_id)?, field.value)))
+            .collect())
+    }
+
+    /// Get the deny settings of addresses in deny list configs
+    async fn get_address_settings(&self, keys: Vec<(ObjectID, SuiAddress)>) -> Result<HashMap<(ObjectID, SuiAddress), Setting>> {
+        let key_type = parse_sui_type_tag("0x2::deny_list::AddressKey")?;
+        let mut field_ids = HashMap::new();
+        for (config_id, address) in keys {
+            let key_bytes = bcs::to_bytes(&AddressKey(address))?;
+            field_ids.insert(derive_dynamic_field_id(config_id, &key_type, &key_bytes)?, (config_id, address));
+        }
+
+        let fields: HashMap<ObjectID, Field<AddressKey, Setting>> = self.get_fields(field_ids.keys().copied().collect()).await?;
+        Ok(fields
+            .into_iter()
+            .filter_map(|(field_id, field)| Some((field_ids.remove(&field_id)?, field.value)))
+            .collect())
+    }
+
+    /// Fetch and decode dynamic field objects, skipping fields that do not exist
+    async fn get_fields<T: DeserializeOwned>(&self, field_ids: Vec<ObjectID>) -> Result<HashMap<ObjectID, T>> {
+        let mut fields = HashMap::new();
+        for chunk in field_ids.chunks(OBJECTS_PAGE_LIMIT) {
+            let resp = self.client
+                .multi_get_object_with_options(chunk.to_vec(), SuiObjectDataOptions::bcs_lossless())
+                .await?;
+            for data in resp.into_iter().filter_map(|item| item.data) {
+                fields.insert(data.object_id, parse_move_object_bcs(&data)?);
+            }
+        }
+        Ok(fields)
+    }
+}
+
+/// Trait for querying the deny lists of regulated coins
+#[async_trait]
+pub trait DenyListQuerier {
+    /// Check whether addresses are on the deny list of a coin, for the current and next epoch
+    ///
+    /// # Arguments
+    /// * `coin_type` - The coin type, e.g. "0x2::sui::SUI"
+    /// * `addresses` - The addresses or SuiNS names to check
+    ///
+    /// # Returns
+    /// * `Result<CoinDenyStatus>` - The deny list state of the coin or an error
+    async fn get_deny_status(&self, coin_type: &str, addresses: &[String]) -> Result<CoinDenyStatus>;
+}
+
+#[async_trait]
+impl DenyListQuerier for SuiQueryZClient {
+    async fn get_deny_status(&self, coin_type: &str, addresses: &[String]) -> Result<CoinDenyStatus> {
+        let query = DenyListQuery::new(Arc::new(self.clone()));
+        query.get_deny_status(coin_type, addresses).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_setting_takes_effect_next_epoch() {
+        let setting = Setting {
+            data: Some(SettingData {
+                newer_value_epoch: 10,
+                newer_value: Some(true),
+                older_value_opt: None,
+            }),
+        };
+
+        assert!(!setting.current_value(10));
+        assert!(setting.next_value());
+        assert!(setting.current_value(11));
+        assert!(!Setting { data: None }.current_value(11));
+    }
+}
@@ -0,0 +1,276 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use move_core_types::language_storage::StructTag;
+use sui_json_rpc_types::{SuiData, SuiObjectData, SuiObjectDataFilter, SuiObjectDataOptions, SuiObjectResponseQuery};
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::dynamic_field::{DynamicFieldType, Field};
+use sui_types::id::{ID, UID};
+use sui_types::{parse_sui_struct_tag, TypeTag, SUI_FRAMEWORK_ADDRESS};
+
+use crate::client::SuiQueryZClient;
+use crate::types::kiosk::{AddressKiosks, KioskContents, KioskItem, KioskListing};
+use crate::utils::object::{parse_move_object_bcs, to_inventory_item};
+
+/// Package of the kiosk extensions (personal kiosks, rules) on mainnet
+pub const MAINNET_KIOSK_EXTENSIONS_PACKAGE: &str = "0x0cb4bcc0560340eb1a1b61b5f1571c1f1ec4c4d18ac26c6e1d4b2b1bdf0e5c0f";
+
+/// Maximum number of objects requested per page or batch
+const OBJECTS_PAGE_LIMIT: usize = 50;
+
+/// Mirror of `0x2::kiosk::KioskOwnerCap`
+#[derive(Deserialize)]
+struct KioskOwnerCap {
+    _id: UID,
+    for_kiosk: ID,
+}
+
+/// Mirror of `personal_kiosk::PersonalKioskCap`
+#[derive(Deserialize)]
+struct PersonalKioskCap {
+    _id: UID,
+    cap: Option<KioskOwnerCap>,
+}
+
+/// Mirror of `0x2::kiosk::Listing`
+#[derive(Deserialize)]
+struct Listing {
+    id: ID,
+    is_exclusive: bool,
+}
+
+/// Mirror of `0x2::kiosk::Lock`
+#[derive(Deserialize)]
+struct Lock {
+    id: ID,
+}
+
+/// A kiosk owned by an address together with the cap proving ownership
+struct OwnedKiosk {
+    kiosk_id: ObjectID,
+    owner_cap_id: ObjectID,
+    is_personal: bool,
+}
+
+/// KioskQuery provides methods to query `0x2::kiosk::Kiosk` objects
+#[derive(Clone)]
+pub struct KioskQuery {
+    client: Arc<SuiQueryZClient>,
+    personal_kiosk_package: ObjectID,
+}
+
+impl KioskQuery {
+    /// Create a new KioskQuery instance
+    ///
+    /// Personal kiosks are discovered through the mainnet kiosk extensions package.
+    ///
+    /// # Arguments
+    /// * `client` - The SuiClient to use for queries
+    ///
+    /// # Returns
+    /// * `KioskQuery` - A new KioskQuery instance
+    pub fn new(client: Arc<SuiQueryZClient>) -> Self {
+        Self {
+            client,
+            personal_kiosk_package: ObjectID::from_hex_literal(MAINNET_KIOSK_EXTENSIONS_PACKAGE)
+                .expect("valid kiosk extensions package id"),
+        }
+    }
+
+    /// Use a different kiosk extensions package to discover personal kiosks
+    ///
+    /// # Arguments
+    /// * `package` - The ID of the package defining `personal_kiosk::PersonalKioskCap`
+    ///
+    /// # Returns
+    /// * `KioskQuery` - The updated KioskQuery instance
+    pub fn with_personal_kiosk_package(mut self, package: ObjectID) -> Self {
+        self.personal_kiosk_package = package;
+        self
+    }
+
+    /// Get all kiosks owned by an address and their contents
+    ///
+    /// # Arguments
+    /// * `address` - The address owning the kiosks
+    ///
+    /// # Returns
+    /// * `Result<AddressKiosks>` - The kiosks and their items or an error
+    pub async fn get_address_kiosks(&self, address: &str) -> Result<AddressKiosks> {
+        let owner = self.client.resolve_address(address).await?;
+
+        let mut kiosks = vec![];
+        for owned_kiosk in self.get_owned_kiosks(owner).await? {
+            let items = self.get_kiosk_items(owned_kiosk.kiosk_id).await?;
+            kiosks.push(KioskContents {
+                kiosk_id: owned_kiosk.kiosk_id,
+                owner_cap_id: owned_kiosk.owner_cap_id,
+                is_personal: owned_kiosk.is_personal,
+                items,
+            });
+        }
+
+        Ok(AddressKiosks { owner, kiosks })
+    }
+
+    /// Get the items placed in a kiosk with their listing and lock status
+    ///
+    /// # Arguments
+    /// * `kiosk_id` - The ID of the kiosk object
+    ///
+    /// # Returns
+    /// * `Result<Vec<KioskItem>>` - The items in the kiosk or an error
+    pub async fn get_kiosk_items(&self, kiosk_id: ObjectID) -> Result<Vec<KioskItem>> {
+        let mut item_ids = vec![];
+        let mut marker_field_ids = vec![];
+        let mut cursor: Option<ObjectID> = None;
+        loop {
+            let fields_resp = self.client
+                .get_dynamic_fields(kiosk_id, cursor, Some(OBJECTS_PAGE_LIMIT))
+                .await?;
+            cursor = fields_resp.next_cursor;
+
+            for field in fields_resp.data {
+                if field.type_ == DynamicFieldType::DynamicObject && is_kiosk_type(&field.name.type_, "Item") {
+                    item_ids.push(field.object_id);
+                } else if is_kiosk_type(&field.name.type_, "Listing") || is_kiosk_type(&field.name.type_, "Lock") {
+                    marker_field_ids.push(field.object_id);
+                }
+            }
+
+            if !fields_resp.has_next_page {
+                break;
+            }
+        }
+
+        // Listings and locks are dynamic fields keyed by the item ID
+        let mut listings: HashMap<ObjectID, KioskListing> = HashMap::new();
+        let mut locked: HashSet<ObjectID> = HashSet::new();
+        for chunk in marker_field_ids.chunks(OBJECTS_PAGE_LIMIT) {
+            let resp = self.client
+                .multi_get_object_with_options(chunk.to_vec(), SuiObjectDataOptions::bcs_lossless())
+                .await?;
+            for data in resp.into_iter().filter_map(|item| item.data) {
+                let is_listing = data.bcs
+                    .as_ref()
+                    .and_then(|bcs| bcs.try_as_move())
+                    .and_then(|object| object.type_.type_params.first())
+                    .is_some_and(|name_type| is_kiosk_type(name_type, "Listing"));
+                if is_listing {
+                    let field: Field<Listing, u64> = parse_move_object_bcs(&data)?;
+                    listings.insert(field.name.id.bytes, KioskListing {
+                        price: field.value,
+                        is_exclusive: field.name.is_exclusive,
+                    });
+                } else {
+                    let field: Field<Lock, bool> = parse_move_object_bcs(&data)?;
+                    locked.insert(field.name.id.bytes);
+                }
+            }
+        }
+
+        let mut items = vec![];
+        for chunk in item_ids.chunks(OBJECTS_PAGE_LIMIT) {
+            let resp = self.client
+                .multi_get_object_with_options(chunk.to_vec(), SuiObjectDataOptions::new().with_type().with_display())
+                .await?;
+            for item in resp.into_iter().filter_map(|item| item.data.and_then(to_inventory_item)) {
+                items.push(KioskItem {
+                    is_locked: locked.contains(&item.object_id),
+                    listing: listings.remove(&item.object_id),
+                    item,
+                });
+            }
+        }
+
+        Ok(items)
+    }
+
+    /// Find the kiosks owned by an address through `KioskOwnerCap` and `PersonalKioskCap` objects
+    async fn get_owned_kiosks(&self, owner: SuiAddress) -> Result<Vec<OwnedKiosk>> {
+        let mut kiosks = vec![];
+
+        let owner_cap_type = parse_sui_struct_tag("0x2::kiosk::KioskOwnerCap")?;
+        for data in self.get_owned_objects_of_type(owner, owner_cap_type).await? {
+            let cap: KioskOwnerCap = parse_move_object_bcs(&data)?;
+            kiosks.push(OwnedKiosk {
+                kiosk_id: cap.for_kiosk.bytes,
+                owner_cap_id: data.object_id,
+                is_personal: false,
+            });
+        }
+
+        let personal_cap_type = parse_sui_struct_tag(&format!(
+            "{}::personal_kiosk::PersonalKioskCap",
+            self.personal_kiosk_package
+        ))?;
+        for data in self.get_owned_objects_of_type(owner, personal_cap_type).await? {
+            let personal_cap: PersonalKioskCap = parse_move_object_bcs(&data)?;
+            // The inner cap is only taken out temporarily while the owner borrows it
+            if let Some(cap) = personal_cap.cap {
+                kiosks.push(OwnedKiosk {
+                    kiosk_id: cap.for_kiosk.bytes,
+                    owner_cap_id: data.object_id,
+                    is_personal: true,
+                });
+            }
+        }
+
+        Ok(kiosks)
+    }
+
+    /// Get all objects of a struct type owned by an address, with their BCS contents
+    async fn get_owned_objects_of_type(&self, owner: SuiAddress, struct_type: StructTag) -> Result<Vec<SuiObjectData>> {
+        let mut objects = vec![];
+        let mut cursor: Option<ObjectID> = None;
+        loop {
+            let query = SuiObjectResponseQuery::new(
+                Some(SuiObjectDataFilter::StructType(struct_type.clone())),
+                Some(SuiObjectDataOptions::bcs_lossless()),
+            );
+            let page = self.client
+                .get_owned_objects(owner, Some(query), cursor, Some(OBJECTS_PAGE_LIMIT))
+                .await?;
+            cursor = page.next_cursor;
+
+            objects.extend(page.data.into_iter().filter_map(|response| response.data));
+
+            if !page.has_next_page {
+                break;
+            }
+        }
+        Ok(objects)
+    }
+}
+
+/// Check whether a type is the given struct of the `0x2::kiosk` module
+fn is_kiosk_type(type_tag: &TypeTag, name: &str) -> bool {
+    matches!(
+        type_tag,
+        TypeTag::Struct(tag)
+            if tag.address == SUI_FRAMEWORK_ADDRESS && tag.module.as_str() == "kiosk" && tag.name.as_str() == name
+    )
+}
+
+/// Trait for querying kiosks
+#[async_trait]
+pub trait KioskQuerier {
+    /// Get all kiosks owned by an address and their contents
+    ///
+    /// # Arguments
+    /// * `address` - The address owning the kiosks
+    ///
+    /// # Returns
+    /// * `Result<AddressKiosks>` - The kiosks and their items or an error
+    async fn get_address_kiosks(&self, address: &str) -> Result<AddressKiosks>;
+}
+
+#[async_trait]
+impl KioskQuerier for SuiQueryZClient {
+    async fn get_address_kiosks(&self, address: &str) -> Result<AddressKiosks> {
+        let query = KioskQuery::new(Arc::new(self.clone()));
+        query.get_address_kiosks(address).await
+    }
+}
@@ -0,0 +1,66 @@
+use anyhow::{anyhow, Result};
+use std::sync::Arc;
+use sui_json_rpc_types::SuiCoinMetadata;
+
+use crate::core_clients::token_registry::TokenRegistry;
+use crate::types::bag::{Balancez, MetadataSource};
+
+/// How balance queries treat coins that have no `CoinMetadata`
+#[derive(Debug, Clone, Default)]
+pub enum MissingMetadataPolicy {
+    /// Fail the whole query
+    #[default]
+    Fail,
+    /// Leave the coin out of the results
+    Skip,
+    /// Report the raw amount with unknown decimals, see `Balancez::is_raw`
+    ///
+    /// Balance maps keyed by symbol leave these coins out; the balance entry APIs include them.
+    RawAmount,
+    /// Use metadata from a token registry, reporting the raw amount for coins not in it
+    Registry(Arc<TokenRegistry>),
+}
+
+impl MissingMetadataPolicy {
+    /// Create a policy falling back to a token registry
+    ///
+    /// Unlike the registry of the coin metadata client, this one is only used for coins
+    /// without on-chain metadata.
+    ///
+    /// # Arguments
+    /// * `registry` - The registry to fall back to
+    ///
+    /// # Returns
+    /// * `MissingMetadataPolicy` - The registry policy
+    pub fn registry(registry: TokenRegistry) -> Self {
+        MissingMetadataPolicy::Registry(Arc::new(registry))
+    }
+
+    /// Build a balance entry, applying the policy when the coin has no metadata
+    ///
+    /// # Arguments
+    /// * `coin_type` - The coin type
+    /// * `raw_balance` - The balance in the coin's smallest unit
+    /// * `metadata` - The on-chain metadata of the coin, if any
+    ///
+    /// # Returns
+    /// * `Result<Option<Balancez>>` - The entry, `None` if the coin is skipped, or an error under `Fail`
+    pub fn balance_entry(&self, coin_type: String, raw_balance: u128, metadata: Option<&SuiCoinMetadata>) -> Result<Option<Balancez>> {
+        if let Some(metadata) = metadata {
+            return Ok(Some(Balancez::from_metadata(coin_type, raw_balance, metadata, MetadataSource::OnChain)));
+        }
+
+        match self {
+            MissingMetadataPolicy::Fail => Err(anyhow!("Coin metadata not found, coin_type: {}", coin_type)),
+            MissingMetadataPolicy::Skip => Ok(None),
+            MissingMetadataPolicy::RawAmount => Ok(Some(Balancez::without_metadata(coin_type, raw_balance))),
+            MissingMetadataPolicy::Registry(registry) => match registry.get(&coin_type) {
+                Some(entry) => Ok(Some(
+                    Balancez::from_metadata(coin_type, raw_balance, &entry.to_metadata(), MetadataSource::Registry)
+                        .with_token_entry(Some(entry)),
+                )),
+                None => Ok(Some(Balancez::without_metadata(coin_type, raw_balance))),
+            },
+        }
+    }
+}
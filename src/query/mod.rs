@@ -2,3 +2,7 @@
 
 pub mod bag; 
 pub mod wallet;
+pub mod kiosk;
+pub mod metadata_policy;
+pub mod coin;
+pub mod deny_list;
@@ -1,29 +1,92 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use async_trait::async_trait;
-use sui_types::base_types::SuiAddress;
-use std::{collections::HashMap, str::FromStr, sync::Arc};
+use futures::{stream, StreamExt, TryStreamExt};
+use sui_json_rpc_types::{
+    Coin, StakeStatus, SuiCoinMetadata, SuiObjectDataOptions, SuiObjectResponseQuery,
+    SuiTransactionBlockResponseOptions, SuiTransactionBlockResponseQuery, TransactionFilter,
+};
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::digests::TransactionDigest;
+use sui_types::object::Owner;
+use std::{collections::{BTreeMap, HashMap, HashSet}, sync::Arc};
 
 use crate::SuiQueryZClient;
+use crate::query::deny_list::DenyListQuery;
+use crate::query::metadata_policy::MissingMetadataPolicy;
+use crate::types::bag::{Balancez, MetadataSource};
+use crate::types::inventory::ObjectInventory;
+use crate::types::wallet::{BalancePoint, CoinFragmentation, CoinTypeBalances, FragmentationOptions, FragmentationReport, MultiWalletBalances, WalletBalances};
+use crate::types::staking::{StakePositionStatus, StakedSuiPosition, ValidatorStakes, WalletNetWorth, WalletStakes};
+use crate::utils::coin::{merge_transactions_needed, normalize_coin_type, STORAGE_REBATE_RATE_BPS, SUI_COIN_TYPE};
+use crate::utils::coin_selection::{self, CoinSelection, CoinSelectionStrategy};
+use crate::utils::object::to_inventory_item;
+
+/// Maximum number of owned objects requested per page
+const OWNED_OBJECTS_PAGE_LIMIT: usize = 50;
+
+/// Maximum number of coin objects requested per page
+const COINS_PAGE_LIMIT: usize = 50;
+
+/// Maximum number of transactions requested per page
+const TRANSACTIONS_PAGE_LIMIT: usize = 50;
 
 pub struct WalletQuery {
     client: Arc<SuiQueryZClient>,
+    missing_metadata_policy: MissingMetadataPolicy,
+    deny_list_checks: bool,
 }
 
 impl WalletQuery {
     pub fn new(client: Arc<SuiQueryZClient>) -> Self {
-        Self { client }
+        Self {
+            client,
+            missing_metadata_policy: MissingMetadataPolicy::default(),
+            deny_list_checks: false,
+        }
+    }
+
+    /// Set how balance queries treat coins without `CoinMetadata`
+    ///
+    /// # Arguments
+    /// * `policy` - The policy to apply, `MissingMetadataPolicy::Fail` by default
+    ///
+    /// # Returns
+    /// * `WalletQuery` - The updated WalletQuery instance
+    pub fn with_missing_metadata_policy(mut self, policy: MissingMetadataPolicy) -> Self {
+        self.missing_metadata_policy = policy;
+        self
+    }
+
+    /// Check the deny lists of regulated coins and mark frozen balance entries
+    ///
+    /// Disabled by default, as it costs extra requests per query.
+    ///
+    /// # Arguments
+    /// * `enabled` - Whether to check the deny lists
+    ///
+    /// # Returns
+    /// * `WalletQuery` - The updated WalletQuery instance
+    pub fn with_deny_list_checks(mut self, enabled: bool) -> Self {
+        self.deny_list_checks = enabled;
+        self
     }
 
     pub async fn get_balance_by_coin_type(&self, address: &str, coin_type: &str) -> Result<u64> {
-        let wallet_address = SuiAddress::from_str(address)?;
+        let wallet_address = self.client.resolve_address(address).await?;
         let balance = self.client.get_coin_balance(wallet_address, coin_type).await?;
         Ok(balance)
     }
 
     pub async fn get_all_balances(&self, address: &str) -> Result<HashMap<String, u64>> {
-        let wallet_address = SuiAddress::from_str(address)?;
-        // println!("Wallet Balance for address: {}", address);
+        let wallet_address = self.client.resolve_address(address).await?;
+        self.get_all_balances_for(wallet_address).await
+    }
 
+    /// Get the raw balances of an address that is already resolved
+    ///
+    /// Queries that report the address use this, so the balances always belong to that address
+    /// even if a name is re-resolved in between.
+    async fn get_all_balances_for(&self, wallet_address: SuiAddress) -> Result<HashMap<String, u64>> {
         let balances = self.client
             .get_coin_balances(wallet_address)
             .await?;
@@ -36,16 +99,57 @@ impl WalletQuery {
         Ok(balances_map)
     }
 
-    pub async fn process_all_balances(&self, balances: HashMap<String, u64>) -> Result<HashMap<String, f64>> {
-        let mut processed_balances = HashMap::new();
+    /// Convert raw balances to balance entries, applying the missing metadata policy
+    ///
+    /// # Arguments
+    /// * `balances` - Raw balances keyed by coin type
+    ///
+    /// # Returns
+    /// * `Result<Vec<Balancez>>` - The balance entries ordered by coin type or an error
+    pub async fn process_balance_entries(&self, balances: HashMap<String, u64>) -> Result<Vec<Balancez>> {
+        let mut entries = Vec::with_capacity(balances.len());
+
+        // Fetch all metadata concurrently up front, so the loop below reads from the cache
+        let coin_types: Vec<String> = balances.keys().cloned().collect();
+        self.client.coin_metadata_client().preload(&coin_types).await;
 
         for (coin_type, balance) in balances {
-            let metadata = self.client.get_coin_metadata(&coin_type).await?;
-            let actual_balance = balance as f64 / 10.0_f64.powi(metadata.decimals as i32);
-            processed_balances.insert(metadata.symbol, actual_balance);
+            let metadata = self.client.try_get_coin_metadata(&coin_type).await?;
+            if let Some(entry) = self.balance_entry(coin_type, balance as u128, metadata.as_ref())? {
+                entries.push(entry);
+            }
         }
 
-        Ok(processed_balances)
+        entries.sort_by(|a, b| a.coin_type.cmp(&b.coin_type));
+        Ok(entries)
+    }
+
+    /// Convert raw balances to balances keyed by symbol
+    ///
+    /// Coins reported without metadata are left out, since their raw amounts cannot be mixed
+    /// with scaled balances; use `process_balance_entries` to get them.
+    pub async fn process_all_balances(&self, balances: HashMap<String, u64>) -> Result<HashMap<String, f64>> {
+        let entries = self.process_balance_entries(balances).await?;
+        Ok(entries
+            .into_iter()
+            .filter(|entry| !entry.is_raw())
+            .map(|entry| (entry.symbol, entry.balance))
+            .collect())
+    }
+
+    /// Get all balances in a wallet as entries marking where their metadata came from
+    ///
+    /// # Arguments
+    /// * `address` - The address of the wallet to query
+    ///
+    /// # Returns
+    /// * `Result<Vec<Balancez>>` - The balance entries ordered by coin type or an error
+    pub async fn get_wallet_balance_entries(&self, address: &str) -> Result<Vec<Balancez>> {
+        let wallet_address = self.client.resolve_address(address).await?;
+        let balances = self.get_all_balances_for(wallet_address).await?;
+        let mut entries = self.process_balance_entries(balances).await?;
+        self.mark_frozen(wallet_address, &mut entries).await?;
+        Ok(entries)
     }
 
     pub async fn get_wallet_balances(&self, address: &str) -> Result<HashMap<String, f64>> {
@@ -54,20 +158,543 @@ impl WalletQuery {
         Ok(processed_balances)
     }
 
+    /// Get balances of a wallet for the requested coin types
+    ///
+    /// Issues a single balance RPC for the wallet regardless of how many coin types are requested.
+    ///
+    /// # Arguments
+    /// * `address` - The address of the wallet to query
+    /// * `coin_types` - The coin types to query
+    ///
+    /// # Returns
+    /// * `Result<CoinTypeBalances>` - The balances split into held, zero and unknown-metadata coin types or an error
+    pub async fn get_balances_by_coin_types(&self, address: &str, coin_types: Vec<String>) -> Result<CoinTypeBalances> {
+        let wallet_address = self.client.resolve_address(address).await?;
+        let held: HashMap<String, (String, u64)> = self.get_all_balances_for(wallet_address)
+            .await?
+            .into_iter()
+            .map(|(coin_type, balance)| (normalize_coin_type(&coin_type), (coin_type, balance)))
+            .collect();
+
+        // Fetch the metadata of the requested held coin types concurrently, so the loop below reads from the cache
+        let held_types: Vec<String> = coin_types
+            .iter()
+            .filter_map(|coin_type| held.get(&normalize_coin_type(coin_type)))
+            .filter(|(_, balance)| *balance > 0)
+            .map(|(held_type, _)| held_type.clone())
+            .collect();
+        self.client.coin_metadata_client().preload(&held_types).await;
+
+        let mut result = CoinTypeBalances {
+            address: wallet_address,
+            balances: vec![],
+            zero_balance: vec![],
+            unknown_metadata: HashMap::new(),
+        };
+        let mut seen = HashSet::new();
+        for coin_type in coin_types {
+            let normalized = normalize_coin_type(&coin_type);
+            if !seen.insert(normalized.clone()) {
+                continue;
+            }
+
+            match held.get(&normalized) {
+                Some((held_type, balance)) if *balance > 0 => {
+                    match self.client.try_get_coin_metadata(held_type).await? {
+                        Some(metadata) => {
+                            let entry = Balancez::from_metadata(held_type.clone(), *balance as u128, &metadata, MetadataSource::OnChain)
+                                .with_token_entry(self.client.coin_metadata_client().token_entry(held_type));
+                            result.balances.push(entry);
+                        }
+                        None => {
+                            result.unknown_metadata.insert(held_type.clone(), *balance);
+                        }
+                    }
+                }
+                _ => result.zero_balance.push(coin_type),
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Get balances of a wallet for the requested coin types, keyed by symbol
+    ///
+    /// Coin types the wallet does not hold are reported with a zero balance. Held and zero-balance
+    /// coin types without metadata are both handled by the missing metadata policy, and raw
+    /// amounts are left out as in `process_all_balances`.
+    ///
+    /// # Arguments
+    /// * `address` - The address of the wallet to query
+    /// * `coin_types` - The coin types to query
+    ///
+    /// # Returns
+    /// * `Result<HashMap<String, f64>>` - The balances keyed by symbol or an error
     pub async fn get_wallet_balances_by_coin_types(&self, address: &str, coin_types: Vec<String>) -> Result<HashMap<String, f64>> {
+        let balances = self.get_balances_by_coin_types(address, coin_types).await?;
+
+        // Fetch the metadata of zero-balance coin types concurrently, so the loop below reads from the cache
+        self.client.coin_metadata_client().preload(&balances.zero_balance).await;
+
+        let mut entries = balances.balances;
+        for (coin_type, balance) in balances.unknown_metadata {
+            if let Some(entry) = self.balance_entry(coin_type, balance as u128, None)? {
+                entries.push(entry);
+            }
+        }
+        for coin_type in balances.zero_balance {
+            let metadata = self.client.try_get_coin_metadata(&coin_type).await?;
+            if let Some(entry) = self.balance_entry(coin_type, 0, metadata.as_ref())? {
+                entries.push(entry);
+            }
+        }
+
+        Ok(entries
+            .into_iter()
+            .filter(|entry| !entry.is_raw())
+            .map(|entry| (entry.symbol, entry.balance))
+            .collect())
+    }
+
+    /// Get the raw balances of a wallet as they were at a past point
+    ///
+    /// Balances are reconstructed by walking back the balance changes of every transaction
+    /// sent by or to the wallet after the requested point. The walk is pinned to the latest
+    /// checkpoint read before the current balances, so transactions landing during the query
+    /// are ignored.
+    ///
+    /// # Arguments
+    /// * `address` - The address of the wallet to query
+    /// * `point` - The checkpoint or timestamp to reconstruct balances at
+    ///
+    /// # Returns
+    /// * `Result<HashMap<String, u64>>` - The raw balances keyed by coin type or an error, also
+    ///   when a reconstructed balance is negative because the history is incomplete
+    pub async fn get_all_balances_at(&self, address: &str, point: BalancePoint) -> Result<HashMap<String, u64>> {
+        let wallet_address = self.client.resolve_address(address).await?;
+        let pinned_checkpoint = self.client.get_latest_checkpoint_sequence_number().await?;
+        let current = self.get_all_balances_for(wallet_address).await?;
+
+        // Keep the coin type spelling of the live query for coin types still held
+        let mut coin_types: HashMap<String, String> = current
+            .keys()
+            .map(|coin_type| (normalize_coin_type(coin_type), coin_type.clone()))
+            .collect();
+        coin_types
+            .entry(normalize_coin_type(SUI_COIN_TYPE))
+            .or_insert_with(|| SUI_COIN_TYPE.to_string());
+
+        let mut deltas: HashMap<String, i128> = HashMap::new();
+        let mut seen: HashSet<TransactionDigest> = HashSet::new();
+        let wallet_owner = Owner::AddressOwner(wallet_address);
+        for filter in [TransactionFilter::FromAddress(wallet_address), TransactionFilter::ToAddress(wallet_address)] {
+            let mut cursor: Option<TransactionDigest> = None;
+            'pages: loop {
+                let query = SuiTransactionBlockResponseQuery::new(
+                    Some(filter.clone()),
+                    Some(SuiTransactionBlockResponseOptions::new().with_balance_changes()),
+                );
+                let page = self.client
+                    .query_transaction_blocks(query, cursor, Some(TRANSACTIONS_PAGE_LIMIT), true)
+                    .await?;
+                cursor = page.next_cursor;
+
+                for transaction in page.data {
+                    // Newer than the current balances, or not yet checkpointed
+                    if transaction.checkpoint.map_or(true, |checkpoint| checkpoint > pinned_checkpoint) {
+                        continue;
+                    }
+                    if !point.precedes(transaction.checkpoint, transaction.timestamp_ms) {
+                        break 'pages;
+                    }
+                    if !seen.insert(transaction.digest) {
+                        continue;
+                    }
+
+                    for change in transaction.balance_changes.unwrap_or_default() {
+                        if change.owner == wallet_owner {
+                            *deltas.entry(change.coin_type.to_canonical_string(true)).or_default() += change.amount;
+                        }
+                    }
+                }
+
+                if !page.has_next_page {
+                    break;
+                }
+            }
+        }
+
+        let current: HashMap<String, u64> = current
+            .into_iter()
+            .map(|(coin_type, balance)| (normalize_coin_type(&coin_type), balance))
+            .collect();
+        let all_coin_types: HashSet<&String> = current.keys().chain(deltas.keys()).collect();
+
         let mut balances = HashMap::new();
-        for coin_type in coin_types {
-            let coin_type = if coin_type == "0x0000000000000000000000000000000000000000000000000000000000000002::sui::SUI" {
-                "0x2::sui::SUI".to_string()
-            } else {
-                coin_type
+        for coin_type in all_coin_types {
+            let balance = current.get(coin_type).copied().unwrap_or(0) as i128
+                - deltas.get(coin_type).copied().unwrap_or(0);
+            if balance < 0 {
+                return Err(anyhow!(
+                    "Reconstructed balance is negative, coin_type: {}, balance: {}; the transaction history is incomplete",
+                    coin_type,
+                    balance
+                ));
+            }
+            if balance > 0 {
+                let key = coin_types.get(coin_type).cloned().unwrap_or_else(|| coin_type.clone());
+                balances.insert(key, balance as u64);
+            }
+        }
+
+        Ok(balances)
+    }
+
+    /// Get all balances in a wallet as they were at a past point
+    ///
+    /// # Arguments
+    /// * `address` - The address of the wallet to query
+    /// * `point` - The checkpoint or timestamp to reconstruct balances at
+    ///
+    /// # Returns
+    /// * `Result<HashMap<String, f64>>` - The balances keyed by symbol or an error
+    pub async fn get_wallet_balances_at(&self, address: &str, point: BalancePoint) -> Result<HashMap<String, f64>> {
+        let balances = self.get_all_balances_at(address, point).await?;
+        self.process_all_balances(balances).await
+    }
+
+    /// Get all coin objects of a type owned by a wallet
+    ///
+    /// # Arguments
+    /// * `address` - The address of the wallet to query
+    /// * `coin_type` - The coin type to list
+    ///
+    /// # Returns
+    /// * `Result<Vec<Coin>>` - The coin objects or an error
+    pub async fn get_coin_objects(&self, address: &str, coin_type: &str) -> Result<Vec<Coin>> {
+        let wallet_address = self.client.resolve_address(address).await?;
+
+        let mut coins = vec![];
+        let mut cursor: Option<ObjectID> = None;
+        loop {
+            let page = self.client
+                .get_coins(wallet_address, Some(coin_type.to_string()), cursor, Some(COINS_PAGE_LIMIT))
+                .await?;
+            cursor = page.next_cursor;
+            coins.extend(page.data);
+
+            if !page.has_next_page {
+                break;
+            }
+        }
+
+        Ok(coins)
+    }
+
+    /// Select coins of a wallet covering a target amount
+    ///
+    /// # Arguments
+    /// * `address` - The address of the wallet to query
+    /// * `coin_type` - The coin type to select
+    /// * `target` - The raw amount to cover
+    /// * `strategy` - The strategy used to pick coins
+    ///
+    /// # Returns
+    /// * `Result<CoinSelection>` - The selected coins or an error if the wallet cannot cover the target
+    pub async fn select_coins(&self, address: &str, coin_type: &str, target: u64, strategy: CoinSelectionStrategy) -> Result<CoinSelection> {
+        let coins = self.get_coin_objects(address, coin_type).await?;
+        coin_selection::select_coins(&coins, target, strategy)
+    }
+
+    /// Analyze how fragmented the coin objects of a wallet are
+    ///
+    /// # Arguments
+    /// * `address` - The address of the wallet to query
+    /// * `options` - Thresholds used to detect tiny coins and fragmented coin types
+    ///
+    /// # Returns
+    /// * `Result<FragmentationReport>` - Coin counts, dust and consolidation estimates per coin type or an error
+    pub async fn get_fragmentation_report(&self, address: &str, options: &FragmentationOptions) -> Result<FragmentationReport> {
+        let wallet_address = self.client.resolve_address(address).await?;
+
+        let mut coins_by_type: BTreeMap<String, Vec<Coin>> = BTreeMap::new();
+        let mut cursor: Option<ObjectID> = None;
+        loop {
+            let page = self.client
+                .get_all_coins(wallet_address, cursor, Some(COINS_PAGE_LIMIT))
+                .await?;
+            cursor = page.next_cursor;
+            for coin in page.data {
+                coins_by_type.entry(coin.coin_type.clone()).or_default().push(coin);
+            }
+
+            if !page.has_next_page {
+                break;
+            }
+        }
+
+        // Consolidating keeps the largest coin of each type and deletes the rest
+        for coins in coins_by_type.values_mut() {
+            coins.sort_by(|a, b| b.balance.cmp(&a.balance));
+        }
+        let merged_coin_ids: Vec<ObjectID> = coins_by_type
+            .values()
+            .flat_map(|coins| coins.iter().skip(1).map(|coin| coin.coin_object_id))
+            .collect();
+
+        let mut storage_rebates: HashMap<ObjectID, u64> = HashMap::new();
+        let rebate_options = SuiObjectDataOptions {
+            show_storage_rebate: true,
+            ..Default::default()
+        };
+        for chunk in merged_coin_ids.chunks(OWNED_OBJECTS_PAGE_LIMIT) {
+            let resp = self.client
+                .multi_get_object_with_options(chunk.to_vec(), rebate_options.clone())
+                .await?;
+            for data in resp.into_iter().filter_map(|item| item.data) {
+                storage_rebates.insert(data.object_id, data.storage_rebate.unwrap_or(0));
+            }
+        }
+
+        let dust_thresholds: HashMap<String, u64> = options.dust_thresholds
+            .iter()
+            .map(|(coin_type, threshold)| (normalize_coin_type(coin_type), *threshold))
+            .collect();
+
+        let mut coin_types = Vec::with_capacity(coins_by_type.len());
+        for (coin_type, coins) in coins_by_type {
+            let total_balance: u128 = coins.iter().map(|coin| coin.balance as u128).sum();
+            let dust_threshold = match dust_thresholds.get(&normalize_coin_type(&coin_type)) {
+                Some(threshold) => *threshold as u128,
+                None => (total_balance as f64 * options.dust_ratio) as u128,
             };
-            let balance = self.get_balance_by_coin_type(address, &coin_type).await?;
-            balances.insert(coin_type, balance);
+            let dust_count = coins.iter().filter(|coin| (coin.balance as u128) < dust_threshold).count();
+            let merged_rebate: u64 = coins
+                .iter()
+                .skip(1)
+                .map(|coin| storage_rebates.get(&coin.coin_object_id).copied().unwrap_or(0))
+                .sum();
+
+            coin_types.push(CoinFragmentation {
+                coin_type,
+                coin_count: coins.len(),
+                total_balance,
+                dust_count,
+                is_fragmented: dust_count >= options.min_dust_coins,
+                merge_transactions: merge_transactions_needed(coins.len()),
+                recoverable_storage_rebate: (merged_rebate as u128 * STORAGE_REBATE_RATE_BPS as u128 / 10_000) as u64,
+            });
         }
 
-        let processed_balances = self.process_all_balances(balances).await?;
-        Ok(processed_balances)
+        Ok(FragmentationReport {
+            address: wallet_address,
+            total_merge_transactions: coin_types.iter().map(|coin_type| coin_type.merge_transactions).sum(),
+            total_recoverable_storage_rebate: coin_types.iter().map(|coin_type| coin_type.recoverable_storage_rebate).sum(),
+            coin_types,
+        })
+    }
+
+    /// Get the staked SUI of a wallet, grouped by validator
+    ///
+    /// # Arguments
+    /// * `address` - The address of the wallet to query
+    ///
+    /// # Returns
+    /// * `Result<WalletStakes>` - The staked SUI positions of the wallet or an error
+    pub async fn get_wallet_stakes(&self, address: &str) -> Result<WalletStakes> {
+        let wallet_address = self.client.resolve_address(address).await?;
+        let delegated_stakes = self.client.get_stakes(wallet_address).await?;
+
+        let mut validators = Vec::with_capacity(delegated_stakes.len());
+        for delegated_stake in delegated_stakes {
+            let positions: Vec<StakedSuiPosition> = delegated_stake.stakes
+                .into_iter()
+                .map(|stake| {
+                    let (status, estimated_reward) = match stake.status {
+                        StakeStatus::Pending => (StakePositionStatus::Pending, 0),
+                        StakeStatus::Active { estimated_reward } => (StakePositionStatus::Active, estimated_reward),
+                        StakeStatus::Unstaked => (StakePositionStatus::Unstaked, 0),
+                    };
+                    StakedSuiPosition {
+                        staked_sui_id: stake.staked_sui_id,
+                        principal: stake.principal,
+                        stake_request_epoch: stake.stake_request_epoch,
+                        stake_active_epoch: stake.stake_active_epoch,
+                        estimated_reward,
+                        status,
+                    }
+                })
+                .collect();
+
+            validators.push(ValidatorStakes {
+                validator_address: delegated_stake.validator_address,
+                staking_pool: delegated_stake.staking_pool,
+                total_principal: positions.iter().map(|position| position.principal).sum(),
+                total_estimated_reward: positions.iter().map(|position| position.estimated_reward).sum(),
+                positions,
+            });
+        }
+
+        Ok(WalletStakes {
+            address: wallet_address,
+            total_principal: validators.iter().map(|validator| validator.total_principal).sum(),
+            total_estimated_reward: validators.iter().map(|validator| validator.total_estimated_reward).sum(),
+            validators,
+        })
+    }
+
+    /// Get all balances in a wallet with staked SUI counted towards the SUI balance
+    ///
+    /// # Arguments
+    /// * `address` - The address of the wallet to query
+    ///
+    /// # Returns
+    /// * `Result<WalletNetWorth>` - The liquid and staked holdings of the wallet or an error
+    pub async fn get_wallet_net_worth(&self, address: &str) -> Result<WalletNetWorth> {
+        let wallet_address = self.client.resolve_address(address).await?;
+        let raw_balances = self.get_all_balances_for(wallet_address).await?;
+        let stakes = self.get_wallet_stakes(&wallet_address.to_string()).await?;
+
+        let sui_coin_type = normalize_coin_type(SUI_COIN_TYPE);
+        let liquid_sui = raw_balances
+            .iter()
+            .find(|(coin_type, _)| normalize_coin_type(coin_type) == sui_coin_type)
+            .map(|(_, balance)| *balance)
+            .unwrap_or(0);
+
+        let mut balances = self.process_all_balances(raw_balances).await?;
+        let staked = stakes.total_principal as u128 + stakes.total_estimated_reward as u128;
+        if staked > 0 {
+            let metadata = self.client.get_coin_metadata(SUI_COIN_TYPE).await?;
+            *balances.entry(metadata.symbol).or_default() += staked as f64 / 10.0_f64.powi(metadata.decimals as i32);
+        }
+
+        Ok(WalletNetWorth {
+            address: stakes.address,
+            liquid_sui,
+            staked_principal: stakes.total_principal,
+            estimated_rewards: stakes.total_estimated_reward,
+            balances,
+        })
+    }
+
+    /// Get the non-coin objects owned by a wallet, rendered via their `Display<T>` templates
+    ///
+    /// # Arguments
+    /// * `address` - The address of the wallet to query
+    ///
+    /// # Returns
+    /// * `Result<ObjectInventory>` - The objects grouped by struct type or an error
+    pub async fn get_object_inventory(&self, address: &str) -> Result<ObjectInventory> {
+        let wallet_address = self.client.resolve_address(address).await?;
+
+        let mut items = vec![];
+        let mut cursor: Option<ObjectID> = None;
+        loop {
+            let query = SuiObjectResponseQuery::new_with_options(SuiObjectDataOptions::new().with_type().with_display());
+            let page = self.client
+                .get_owned_objects(wallet_address, Some(query), cursor, Some(OWNED_OBJECTS_PAGE_LIMIT))
+                .await?;
+            cursor = page.next_cursor;
+
+            items.extend(page.data.into_iter().filter_map(|response| response.data.and_then(to_inventory_item)));
+
+            if !page.has_next_page {
+                break;
+            }
+        }
+
+        Ok(ObjectInventory::from_items(wallet_address, items))
+    }
+
+    /// Get balances of many wallets concurrently
+    ///
+    /// Addresses may be SuiNS names. Coin metadata is fetched once per distinct coin type across all wallets.
+    ///
+    /// # Arguments
+    /// * `addresses` - The addresses of the wallets to query
+    /// * `concurrency` - The maximum number of requests in flight at once
+    ///
+    /// # Returns
+    /// * `Result<MultiWalletBalances>` - Per-address balances and aggregated totals or an error
+    pub async fn get_multi_wallet_balances(&self, addresses: &[String], concurrency: usize) -> Result<MultiWalletBalances> {
+        let concurrency = concurrency.max(1);
+
+        let raw_balances: Vec<(SuiAddress, Option<String>, HashMap<String, u64>)> = stream::iter(addresses)
+            .map(|address| async move {
+                let wallet_address = self.client.resolve_address(address).await?;
+                let balances = self.get_all_balances_for(wallet_address).await?;
+                // Names are informational, so nodes without SuiNS support still return balances
+                let primary_name = self.client.get_primary_name(wallet_address).await.ok().flatten();
+                Ok::<_, anyhow::Error>((wallet_address, primary_name, balances))
+            })
+            .buffered(concurrency)
+            .try_collect()
+            .await?;
+
+        let coin_types: HashSet<String> = raw_balances
+            .iter()
+            .flat_map(|(_, _, balances)| balances.keys().cloned())
+            .collect();
+
+        let metadata: HashMap<String, Option<SuiCoinMetadata>> = stream::iter(coin_types)
+            .map(|coin_type| async move {
+                let metadata = self.client.try_get_coin_metadata(&coin_type).await?;
+                Ok::<_, anyhow::Error>((coin_type, metadata))
+            })
+            .buffer_unordered(concurrency)
+            .try_collect()
+            .await?;
+
+        let mut totals: BTreeMap<String, u128> = BTreeMap::new();
+        let mut wallets = Vec::with_capacity(raw_balances.len());
+        for (address, primary_name, balances) in raw_balances {
+            let mut entries = Vec::with_capacity(balances.len());
+            for (coin_type, balance) in balances {
+                *totals.entry(coin_type.clone()).or_default() += balance as u128;
+                let coin_metadata = metadata[&coin_type].as_ref();
+                if let Some(entry) = self.balance_entry(coin_type, balance as u128, coin_metadata)? {
+                    entries.push(entry);
+                }
+            }
+            entries.sort_by(|a, b| a.coin_type.cmp(&b.coin_type));
+            self.mark_frozen(address, &mut entries).await?;
+            wallets.push(WalletBalances { address, primary_name, balances: entries });
+        }
+
+        let mut total_entries = Vec::with_capacity(totals.len());
+        for (coin_type, balance) in totals {
+            let coin_metadata = metadata[&coin_type].as_ref();
+            if let Some(entry) = self.balance_entry(coin_type, balance, coin_metadata)? {
+                total_entries.push(entry);
+            }
+        }
+
+        Ok(MultiWalletBalances { wallets, totals: total_entries })
+    }
+
+    /// Mark the entries whose coin the address cannot use, if deny list checks are enabled
+    async fn mark_frozen(&self, address: SuiAddress, entries: &mut [Balancez]) -> Result<()> {
+        if !self.deny_list_checks || entries.is_empty() {
+            return Ok(());
+        }
+
+        let coin_types: Vec<String> = entries.iter().map(|entry| entry.coin_type.clone()).collect();
+        let frozen = DenyListQuery::new(self.client.clone())
+            .get_frozen_coin_types(address, &coin_types)
+            .await?;
+        for entry in entries {
+            entry.frozen = frozen.contains(&entry.coin_type);
+        }
+        Ok(())
+    }
+
+    /// Build a balance entry under the missing metadata policy, marking token registry entries
+    fn balance_entry(&self, coin_type: String, raw_balance: u128, metadata: Option<&SuiCoinMetadata>) -> Result<Option<Balancez>> {
+        let coin_metadata_client = self.client.coin_metadata_client();
+        let token_entry = coin_metadata_client.token_entry(&coin_type).cloned();
+        let entry = self.missing_metadata_policy.balance_entry(coin_type, raw_balance, metadata)?;
+        Ok(entry.map(|entry| entry.with_token_entry(token_entry.as_ref())))
     }
 }
 
@@ -91,6 +718,44 @@ pub trait WalletQuerier {
     /// # Returns
     /// * `Result<HashMap<String, f64>>` - The balances in the wallet or an error
     async fn get_wallet_balances_by_coin_types(&self, address: &str, coin_types: Vec<String>) -> Result<HashMap<String, f64>>;
+
+    /// Get balances of a wallet for the requested coin types
+    ///
+    /// # Arguments
+    /// * `address` - The address of the wallet to query
+    /// * `coin_types` - The coin types to query
+    ///
+    /// # Returns
+    /// * `Result<CoinTypeBalances>` - The balances split into held, zero and unknown-metadata coin types or an error
+    async fn get_balances_by_coin_types(&self, address: &str, coin_types: Vec<String>) -> Result<CoinTypeBalances>;
+
+    /// Get balances of many wallets concurrently
+    ///
+    /// # Arguments
+    /// * `addresses` - The addresses of the wallets to query
+    /// * `concurrency` - The maximum number of requests in flight at once
+    ///
+    /// # Returns
+    /// * `Result<MultiWalletBalances>` - Per-address balances and aggregated totals or an error
+    async fn get_multi_wallet_balances(&self, addresses: &[String], concurrency: usize) -> Result<MultiWalletBalances>;
+
+    /// Get the staked SUI of a wallet, grouped by validator
+    ///
+    /// # Arguments
+    /// * `address` - The address of the wallet to query
+    ///
+    /// # Returns
+    /// * `Result<WalletStakes>` - The staked SUI positions of the wallet or an error
+    async fn get_wallet_stakes(&self, address: &str) -> Result<WalletStakes>;
+
+    /// Get all balances in a wallet with staked SUI counted towards the SUI balance
+    ///
+    /// # Arguments
+    /// * `address` - The address of the wallet to query
+    ///
+    /// # Returns
+    /// * `Result<WalletNetWorth>` - The liquid and staked holdings of the wallet or an error
+    async fn get_wallet_net_worth(&self, address: &str) -> Result<WalletNetWorth>;
 }
 
 #[async_trait]
@@ -104,4 +769,24 @@ impl WalletQuerier for SuiQueryZClient {
         let query = WalletQuery::new(Arc::new(self.clone()));
         query.get_wallet_balances_by_coin_types(address, coin_types).await
     }
+
+    async fn get_balances_by_coin_types(&self, address: &str, coin_types: Vec<String>) -> Result<CoinTypeBalances> {
+        let query = WalletQuery::new(Arc::new(self.clone()));
+        query.get_balances_by_coin_types(address, coin_types).await
+    }
+
+    async fn get_multi_wallet_balances(&self, addresses: &[String], concurrency: usize) -> Result<MultiWalletBalances> {
+        let query = WalletQuery::new(Arc::new(self.clone()));
+        query.get_multi_wallet_balances(addresses, concurrency).await
+    }
+
+    async fn get_wallet_stakes(&self, address: &str) -> Result<WalletStakes> {
+        let query = WalletQuery::new(Arc::new(self.clone()));
+        query.get_wallet_stakes(address).await
+    }
+
+    async fn get_wallet_net_worth(&self, address: &str) -> Result<WalletNetWorth> {
+        let query = WalletQuery::new(Arc::new(self.clone()));
+        query.get_wallet_net_worth(address).await
+    }
 } 
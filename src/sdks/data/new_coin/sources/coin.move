@@ -1,28 +1,28 @@
 // SPDX-License-Identifier: Apache-2.0
 
-module symbol::symbol;
+module {{module_name}}::{{module_name}};
 
 use sui::coin;
 
 /// Witness of the coin
-public struct SYMBOL has drop {}
+public struct {{witness}} has drop {}
 
 /// Register the trusted currency to acquire its `TreasuryCap`. Because
 /// this is a module initializer, it ensures the currency only gets
 /// registered once.
-fun init(witness: SYMBOL, ctx: &mut TxContext) {
+fun init(witness: {{witness}}, ctx: &mut TxContext) {
     // Get a treasury cap for the coin and give it to the transaction
     // sender
-    let (mut treasury_cap, metadata) = coin::create_currency<SYMBOL>(
+    let (mut treasury_cap, metadata) = coin::create_currency<{{witness}}>(
         witness,
-        DECIMALS,
-        b"SYMBOL",
-        b"NAME",
-        b"DESCRIPTION",
-        option::some(sui::url::new_unsafe_from_bytes(b"ICON")),
+        {{decimals}},
+        b"{{symbol}}",
+        b"{{name}}",
+        b"{{description}}",
+        {{icon_url}},
         ctx,
     );
     transfer::public_freeze_object(metadata);
-    sui::coin::mint_and_transfer(&mut treasury_cap, SUPPLY, @COIN_RECIPIENT, ctx);
-    transfer::public_transfer(treasury_cap, @CAP_RECIPIENT)
+    sui::coin::mint_and_transfer(&mut treasury_cap, {{initial_supply}}, @{{mint_authority}}, ctx);
+    transfer::public_transfer(treasury_cap, @{{freeze_authority}})
 }
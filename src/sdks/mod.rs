@@ -1,2 +1,3 @@
 pub mod publish_contract;
-
+pub mod template;
+pub mod token_launcher;
@@ -1,11 +1,23 @@
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+use std::str::FromStr;
 use sui_move_build::BuildConfig;
+use move_core_types::identifier::Identifier;
+use sui_types::base_types::{ObjectID, SuiAddress};
 use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::transaction::Argument;
+use sui_types::SUI_FRAMEWORK_PACKAGE_ID;
 
-const PACKAGE_PATH: &str = "/data/suiz/new_coins";
+use super::template::{escape_move_bytes, render};
+
+/// Files of the coin template embedded in the crate, as (path relative to the package, content)
+const EMBEDDED_TEMPLATE: &[(&str, &str)] = &[
+    ("Move.toml", include_str!("data/new_coin/Move.toml")),
+    ("sources/coin.move", include_str!("data/new_coin/sources/coin.move")),
+];
 
 pub struct NewCoinParams {
     decimals: u8,
@@ -17,6 +29,7 @@ pub struct NewCoinParams {
     mint_authority: String,
 }
 
+#[derive(Debug, Clone)]
 pub struct TokenParams {
     pub name: String,
     pub symbol: String,
@@ -28,22 +41,136 @@ pub struct TokenParams {
     pub url: String,
 }
 
+/// Highest decimals accepted for a new coin
+pub const MAX_DECIMALS: u8 = 18;
+
+/// Move keywords, which cannot be used as module names
+const MOVE_KEYWORDS: &[&str] = &[
+    "abort", "acquires", "as", "break", "const", "continue", "copy", "else", "enum", "false", "for", "friend", "fun",
+    "has", "if", "let", "loop", "macro", "match", "module", "move", "mut", "native", "phantom", "public", "return",
+    "Self", "spec", "struct", "true", "type", "use", "while",
+];
+
+/// Named addresses declared by the framework packages, which the module name must not reuse
+const RESERVED_ADDRESS_NAMES: &[&str] = &["std", "sui", "sui_system", "bridge", "deepbook"];
+
+impl TokenParams {
+    /// Check the params before they are rendered into a package
+    ///
+    /// # Returns
+    /// * `Result<()>` - An error describing the first invalid param
+    pub fn validate(&self) -> Result<()> {
+        let mut chars = self.symbol.chars();
+        let is_identifier = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !is_identifier {
+            return Err(anyhow!("Symbol must start with a letter and contain only letters, digits and '_', got: {}", self.symbol));
+        }
+        if MOVE_KEYWORDS.contains(&self.module_name().as_str()) {
+            return Err(anyhow!("Symbol cannot be a Move keyword, got: {}", self.symbol));
+        }
+        if RESERVED_ADDRESS_NAMES.contains(&self.module_name().as_str()) {
+            return Err(anyhow!("Symbol cannot be a framework address name, got: {}", self.symbol));
+        }
+        if self.name.trim().is_empty() {
+            return Err(anyhow!("Name cannot be empty"));
+        }
+        if self.decimals > MAX_DECIMALS {
+            return Err(anyhow!("Decimals must be at most {}, got: {}", MAX_DECIMALS, self.decimals));
+        }
+        SuiAddress::from_str(&self.mint_authority)
+            .map_err(|e| anyhow!("Invalid mint authority {}: {}", self.mint_authority, e))?;
+        SuiAddress::from_str(&self.freeze_authority)
+            .map_err(|e| anyhow!("Invalid freeze authority {}: {}", self.freeze_authority, e))?;
+        if !self.url.is_empty() {
+            // `sui::url` stores the URL as an ASCII string and aborts on any other byte
+            let has_scheme = self.url.starts_with("https://") || self.url.starts_with("http://");
+            if !has_scheme || !self.url.is_ascii() || self.url.chars().any(|c| c.is_whitespace() || c.is_control()) {
+                return Err(anyhow!("Icon URL must be an ASCII http(s) URL without whitespace, got: {}", self.url));
+            }
+        }
+        Ok(())
+    }
+
+    /// The Move module and address name of the coin package, the lowercase symbol
+    pub fn module_name(&self) -> String {
+        self.symbol.to_lowercase()
+    }
+
+    /// The one-time witness of the coin, which must be the uppercase module name
+    pub fn witness(&self) -> String {
+        self.symbol.to_uppercase()
+    }
+
+    /// Values of the template placeholders, escaped for the place they are rendered in
+    ///
+    /// # Returns
+    /// * `Result<HashMap<&str, String>>` - The values using the placeholder name as key or an error if the params are invalid
+    pub fn template_values(&self) -> Result<HashMap<&'static str, String>> {
+        self.validate()?;
+
+        let icon_url = if self.url.is_empty() {
+            "option::none()".to_string()
+        } else {
+            format!("option::some(sui::url::new_unsafe_from_bytes(b\"{}\"))", escape_move_bytes(&self.url))
+        };
+
+        Ok(HashMap::from([
+            ("package_name", self.symbol.clone()),
+            ("module_name", self.module_name()),
+            ("witness", self.witness()),
+            ("decimals", self.decimals.to_string()),
+            ("symbol", escape_move_bytes(&self.symbol)),
+            ("name", escape_move_bytes(&self.name)),
+            ("description", escape_move_bytes(&self.description)),
+            ("icon_url", icon_url),
+            ("initial_supply", self.initial_supply.to_string()),
+            ("mint_authority", SuiAddress::from_str(&self.mint_authority)?.to_string()),
+            ("freeze_authority", SuiAddress::from_str(&self.freeze_authority)?.to_string()),
+        ]))
+    }
+}
+
 pub struct ContractPublisher {
-    template_path: PathBuf,
+    /// Template directory overriding the embedded template
+    template_path: Option<PathBuf>,
     output_path: PathBuf,
 }
 
 impl ContractPublisher {
-    pub fn new(template_path_str: &str, output_path_str: &str) -> Self {
-        let template_path = PathBuf::from(template_path_str);
-        let output_path = PathBuf::from(output_path_str);
+    /// Create a new ContractPublisher rendering the embedded coin template
+    ///
+    /// # Arguments
+    /// * `output_path_str` - The directory new packages are written to
+    ///
+    /// # Returns
+    /// * `ContractPublisher` - A new publisher instance
+    pub fn new(output_path_str: &str) -> Self {
         Self {
-            template_path,
-            output_path,
+            template_path: None,
+            output_path: PathBuf::from(output_path_str),
         }
     }
 
+    /// Render a template directory from disk instead of the embedded template
+    ///
+    /// # Arguments
+    /// * `template_path_str` - The template directory, containing `Move.toml` and `sources/coin.move`
+    ///
+    /// # Returns
+    /// * `ContractPublisher` - The updated publisher instance
+    pub fn with_template_path(mut self, template_path_str: &str) -> Self {
+        self.template_path = Some(PathBuf::from(template_path_str));
+        self
+    }
+
+    /// The directory new packages are written to
+    pub fn output_path(&self) -> &Path {
+        &self.output_path
+    }
+
     pub fn publish_token(&self, params: TokenParams) -> Result<String> {
+        let values = params.template_values()?;
         fs::create_dir_all(&self.output_path)?;
 
         let timestamp = SystemTime::now()
@@ -51,23 +178,41 @@ impl ContractPublisher {
             .as_secs();
         
         // 创建新的项目目录
-        let project_name = format!("{}_{}", params.symbol.to_lowercase(), timestamp);
-        let new_project_path = Path::new(&self.output_path).join(&project_name);
+        let (project_name, new_project_path) = self.create_project_dir(&params.module_name(), timestamp)?;
         
         // 复制模板目录
-        self.copy_template(&self.template_path, &new_project_path)?;
+        match &self.template_path {
+            Some(template_path) => self.copy_template(template_path, &new_project_path)?,
+            None => self.write_embedded_template(&new_project_path)?,
+        }
         
         // 替换文件内容
-        self.replace_token_info(&new_project_path, &params)?;
+        self.replace_token_info(&new_project_path, &values)?;
         
         Ok(project_name)
     }
 
-    fn copy_template(&self, src: &Path, dst: &Path) -> Result<()> {
-        if dst.exists() {
-            fs::remove_dir_all(dst)?;
+    /// Create an empty directory for a new package, never reusing an existing one
+    ///
+    /// Launches of the same symbol within the same second get a numbered suffix, so
+    /// concurrent launches cannot overwrite each other's package.
+    fn create_project_dir(&self, module_name: &str, timestamp: u64) -> Result<(String, PathBuf)> {
+        let mut attempt = 0u32;
+        loop {
+            let project_name = match attempt {
+                0 => format!("{}_{}", module_name, timestamp),
+                _ => format!("{}_{}_{}", module_name, timestamp, attempt),
+            };
+            let project_path = self.output_path.join(&project_name);
+            match fs::create_dir(&project_path) {
+                Ok(()) => return Ok((project_name, project_path)),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => attempt += 1,
+                Err(e) => return Err(e.into()),
+            }
         }
-        
+    }
+
+    fn copy_template(&self, src: &Path, dst: &Path) -> Result<()> {
         fs::create_dir_all(dst)?;
         
         for entry in fs::read_dir(src)? {
@@ -86,84 +231,233 @@ impl ContractPublisher {
         Ok(())
     }
 
-    fn replace_token_info(&self, project_path: &Path, params: &TokenParams) -> Result<()> {
-        // 处理 Move.toml
-        let move_toml_path = project_path.join("Move.toml");
-        if move_toml_path.exists() {
-            let mut content = fs::read_to_string(&move_toml_path)?;
-            // 替换包名中的 SYMBOL
-            content = content.replace("SYMBOL", &params.symbol);
-            // 替换地址部分
-            let lower_symbol = params.symbol.to_lowercase();
-            content = content.replace("symbol = \"0x0\"", &format!("{} = \"0x0\"", lower_symbol));
-            fs::write(&move_toml_path, content)?;
+    fn write_embedded_template(&self, dst: &Path) -> Result<()> {
+        for (relative_path, content) in EMBEDDED_TEMPLATE {
+            let path = dst.join(relative_path);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, content)?;
         }
 
-        // 处理 coin.move
-        let sources_path = project_path.join("sources");
-        let coin_move_path = sources_path.join("coin.move");
-        
-        if coin_move_path.exists() {
-            let mut content = fs::read_to_string(&coin_move_path)?;
-            
-            // 替换模块名中的 symbol
-            let lower_symbol = params.symbol.to_lowercase();
-            content = content.replace("symbol::symbol", &format!("{}::{}", lower_symbol, lower_symbol));
-            
-            // 替换代币结构体名称
-            content = content.replace("SYMBOL", &params.symbol);
-            
-            // 替换代币信息
-            content = content.replace("DECIMALS", &params.decimals.to_string());
-            content = content.replace("SYMBOL", &params.symbol);
-            content = content.replace("NAME", &params.name);
-            content = content.replace("DESCRIPTION", &params.description);
-            content = content.replace("SUPPLY", &params.initial_supply.to_string());
-            
-            // 替换权限地址
-            content = content.replace("COIN_RECIPIENT", &params.mint_authority);
-            content = content.replace("CAP_RECIPIENT", &params.freeze_authority);
-            
-            // 替换图标 URL
-            content = content.replace("ICON", &params.url);
-            
-            fs::write(&coin_move_path, content)?;
+        Ok(())
+    }
+
+    fn replace_token_info(&self, project_path: &Path, values: &HashMap<&str, String>) -> Result<()> {
+        // 渲染 Move.toml 和 coin.move 中的占位符
+        for file_path in [project_path.join("Move.toml"), project_path.join("sources").join("coin.move")] {
+            if file_path.exists() {
+                let content = fs::read_to_string(&file_path)?;
+                fs::write(&file_path, render(&content, values)?)?;
+            }
         }
-        
+
         Ok(())
     }
 }
 
-pub fn publish_contract(_ptb: &mut ProgrammableTransactionBuilder, path_str: &str) -> Result<(), anyhow::Error> {
-    let mut path = PathBuf::from(path_str);
-    let compiled_package = BuildConfig::new_for_testing().build(&path)?;
-    let compiled_modules_bytes = compiled_package
-        .get_package_base64(false)
-        .into_iter()
-        .map(|b| b.to_vec().unwrap())
-        .collect::<Vec<_>>();
+/// What to do with the `UpgradeCap` of a published package
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpgradeCapPolicy {
+    /// Transfer the cap to an address, which can then upgrade the package
+    Transfer(SuiAddress),
+    /// Make the package immutable by destroying the cap
+    Immutable,
+    /// Leave the cap to the caller, who must use the returned argument in a later command
+    Return,
+}
+
+/// Compile a Move package for publishing
+///
+/// The dependency lock file `Move.lock` is written to the package directory, as `sui move build` does.
+///
+/// # Arguments
+/// * `path_str` - The directory containing the package's `Move.toml`
+///
+/// # Returns
+/// * `Result<(Vec<Vec<u8>>, Vec<ObjectID>)>` - The module bytes and the IDs of the dependencies or an error
+pub fn get_package_bytes(path_str: &str) -> Result<(Vec<Vec<u8>>, Vec<ObjectID>)> {
+    let package_path = Path::new(path_str);
+    let mut build_config = BuildConfig::default();
+    build_config.config.lock_file = Some(package_path.join("Move.lock"));
+    build_config.run_bytecode_verifier = true;
+    let compiled_package = build_config.build(package_path)?;
+    let compiled_modules_bytes = compiled_package.get_package_bytes(false);
     let dependencies = compiled_package.get_dependency_storage_package_ids();
+    Ok((compiled_modules_bytes, dependencies))
+}
 
-    let mut builder = ProgrammableTransactionBuilder::new();
-    builder.publish_immutable(compiled_modules_bytes, dependencies);
-    Ok(())
+/// Append the publish of a Move package to a transaction
+///
+/// # Arguments
+/// * `ptb` - The transaction builder to append the commands to
+/// * `path_str` - The directory containing the package's `Move.toml`
+/// * `upgrade_cap_policy` - What to do with the `UpgradeCap` of the package
+///
+/// # Returns
+/// * `Result<Argument>` - The result of the publish command, i.e. the `UpgradeCap`, or an error
+pub fn publish_contract(ptb: &mut ProgrammableTransactionBuilder, path_str: &str, upgrade_cap_policy: UpgradeCapPolicy) -> Result<Argument> {
+    let (compiled_modules_bytes, dependencies) = get_package_bytes(path_str)?;
+    publish_package_bytes(ptb, compiled_modules_bytes, dependencies, upgrade_cap_policy)
+}
+
+/// Append the publish of already compiled modules to a transaction
+///
+/// # Arguments
+/// * `ptb` - The transaction builder to append the commands to
+/// * `compiled_modules_bytes` - The bytes of the package's modules
+/// * `dependencies` - The IDs of the packages the modules depend on
+/// * `upgrade_cap_policy` - What to do with the `UpgradeCap` of the package
+///
+/// # Returns
+/// * `Result<Argument>` - The result of the publish command, i.e. the `UpgradeCap`, or an error
+pub fn publish_package_bytes(
+    ptb: &mut ProgrammableTransactionBuilder,
+    compiled_modules_bytes: Vec<Vec<u8>>,
+    dependencies: Vec<ObjectID>,
+    upgrade_cap_policy: UpgradeCapPolicy,
+) -> Result<Argument> {
+    let upgrade_cap = ptb.publish_upgradeable(compiled_modules_bytes, dependencies);
+
+    match upgrade_cap_policy {
+        UpgradeCapPolicy::Transfer(recipient) => ptb.transfer_arg(recipient, upgrade_cap),
+        UpgradeCapPolicy::Immutable => {
+            ptb.programmable_move_call(
+                SUI_FRAMEWORK_PACKAGE_ID,
+                Identifier::new("package")?,
+                Identifier::new("make_immutable")?,
+                vec![],
+                vec![upgrade_cap],
+            );
+        }
+        UpgradeCapPolicy::Return => {}
+    }
+
+    Ok(upgrade_cap)
 }
 
 // 使用示例
 #[cfg(test)]
 mod tests {
-    use sui_sdk::{SuiClient, SuiClientBuilder};
+    use sui_sdk::SuiClientBuilder;
     use std::str::FromStr;
-    use sui_types::{base_types::SuiAddress, transaction::{TransactionData, TransactionKind}};
+    use sui_json_rpc_types::SuiTransactionBlockEffectsAPI;
+    use sui_types::{base_types::SuiAddress, transaction::{Command, TransactionData, TransactionKind}};
 
     use super::*;
 
+    #[test]
+    fn test_template_values() {
+        let mut params = TokenParams {
+            name: "My \"SUPPLY\" Token".to_string(),
+            symbol: "Mtk".to_string(),
+            decimals: 9,
+            description: "NAME and {{symbol}}".to_string(),
+            initial_supply: 1000000000,
+            freeze_authority: "0x2".to_string(),
+            mint_authority: "0xe5da95de00f8ef23fac3f16528bb23a264ad263fb253f95a95089f50350d97f3".to_string(),
+            url: String::new(),
+        };
+
+        let template = "module {{module_name}}::{{module_name}}; {{witness}} b\"{{name}}\" b\"{{description}}\" {{icon_url}} @{{freeze_authority}}";
+        let rendered = render(template, &params.template_values().unwrap()).unwrap();
+        assert_eq!(
+            rendered,
+            format!(
+                "module mtk::mtk; MTK b\"My \\\"SUPPLY\\\" Token\" b\"NAME and {{{{symbol}}}}\" option::none() @0x{}2",
+                "0".repeat(63)
+            )
+        );
+
+        params.symbol = "1MTK".to_string();
+        assert!(params.validate().is_err());
+        params.symbol = "MOVE".to_string();
+        assert!(params.validate().is_err());
+        params.symbol = "HAS".to_string();
+        assert!(params.validate().is_err());
+        params.symbol = "SUI".to_string();
+        assert!(params.validate().is_err());
+        params.symbol = "Std".to_string();
+        assert!(params.validate().is_err());
+        params.symbol = "MTK".to_string();
+        params.decimals = 19;
+        assert!(params.validate().is_err());
+        params.decimals = 9;
+        params.mint_authority = "alice".to_string();
+        assert!(params.validate().is_err());
+        params.mint_authority = "0x2".to_string();
+        params.url = "javascript:alert(1)".to_string();
+        assert!(params.validate().is_err());
+        params.url = "https://café.io/logo.png".to_string();
+        assert!(params.validate().is_err());
+        params.url = "https://cafe.io/logo.png".to_string();
+        assert!(params.validate().is_ok());
+    }
+
+    #[test]
+    fn test_render_embedded_template() {
+        let output_path = std::env::temp_dir().join("queryz_embedded_template");
+        let publisher = ContractPublisher::new(output_path.to_str().unwrap());
+        let params = TokenParams {
+            name: "My Token".to_string(),
+            symbol: "MTK".to_string(),
+            decimals: 9,
+            description: String::new(),
+            initial_supply: 1000000000,
+            freeze_authority: "0x2".to_string(),
+            mint_authority: "0x2".to_string(),
+            url: String::new(),
+        };
+
+        let project_path = output_path.join(publisher.publish_token(params.clone()).unwrap());
+        let move_toml = fs::read_to_string(project_path.join("Move.toml")).unwrap();
+        let coin_move = fs::read_to_string(project_path.join("sources").join("coin.move")).unwrap();
+        assert!(move_toml.contains("mtk = \"0x0\""));
+        assert!(coin_move.contains("module mtk::mtk;"));
+        assert!(!coin_move.contains("{{"));
+
+        // A second package of the same symbol never reuses the first one's directory
+        let second_path = output_path.join(publisher.publish_token(params).unwrap());
+        assert_ne!(second_path, project_path);
+        assert!(project_path.join("Move.toml").exists());
+
+        fs::remove_dir_all(project_path).unwrap();
+        fs::remove_dir_all(second_path).unwrap();
+    }
+
+    #[test]
+    fn test_publish_commands_per_policy() {
+        let recipient = SuiAddress::random_for_testing_only();
+        for policy in [UpgradeCapPolicy::Transfer(recipient), UpgradeCapPolicy::Immutable, UpgradeCapPolicy::Return] {
+            let mut builder = ProgrammableTransactionBuilder::new();
+            publish_package_bytes(&mut builder, vec![vec![0xa1, 0x1c, 0xeb, 0x0b]], vec![SUI_FRAMEWORK_PACKAGE_ID], policy).unwrap();
+            let commands = builder.finish().commands;
+
+            assert!(matches!(commands.first(), Some(Command::Publish(modules, dependencies)) if modules.len() == 1 && dependencies == &vec![SUI_FRAMEWORK_PACKAGE_ID]));
+            match policy {
+                UpgradeCapPolicy::Transfer(_) => {
+                    assert!(matches!(commands.as_slice(), [_, Command::TransferObjects(objects, _)] if objects == &vec![Argument::Result(0)]));
+                }
+                UpgradeCapPolicy::Immutable => {
+                    assert!(matches!(
+                        commands.as_slice(),
+                        [_, Command::MoveCall(call)] if call.package == SUI_FRAMEWORK_PACKAGE_ID
+                            && call.module.as_str() == "package"
+                            && call.function.as_str() == "make_immutable"
+                            && call.arguments == vec![Argument::Result(0)]
+                    ));
+                }
+                UpgradeCapPolicy::Return => assert_eq!(commands.len(), 1),
+            }
+        }
+    }
+
+    /// Needs network access to testnet and to the Sui framework sources the package depends on
     #[tokio::test]
+    #[ignore]
     async fn test_publish_token() {
-        let publisher = ContractPublisher::new(
-            "/home/ccbond/suiz_data/new_coin",
-            "/home/ccbond/suiz_data/published",
-        );
+        let output_path = std::env::temp_dir().join("queryz_published");
+        let publisher = ContractPublisher::new(output_path.to_str().unwrap());
 
         let params = TokenParams {
             name: "My Token".to_string(),
@@ -176,9 +470,7 @@ mod tests {
             url: "https://miucoin.io/favicon.ico".to_string(),
         };
 
-        let project_name_result = publisher.publish_token(params);
-        println!("project_name: {:?}", project_name_result);
-        assert!(project_name_result.is_ok());
+        let project_name = publisher.publish_token(params).unwrap();
 
         let sui_client = SuiClientBuilder::default()
              .build_testnet()
@@ -187,17 +479,22 @@ mod tests {
         let address = SuiAddress::from_str("0x823e17a9a03e56f26700d8ebf23a3644bd65bfda26272d55c3e7148f77c887c1").unwrap();
 
         let mut builder = ProgrammableTransactionBuilder::new();
-        let project_path = Path::new("/home/ccbond/suiz_data/published").join(project_name_result.unwrap());
-        publish_contract(&mut builder, project_path.to_str().unwrap()).unwrap();
-        let publish = TransactionKind::programmable(builder.finish());
-        let transaction_bytes =
-            TransactionData::new_with_gas_coins(publish, address, vec![], 100000000, 1000);
-    
+        let project_path = output_path.join(project_name);
+        publish_contract(&mut builder, project_path.to_str().unwrap(), UpgradeCapPolicy::Transfer(address)).unwrap();
+        let ptb = builder.finish();
+        assert!(matches!(ptb.commands.as_slice(), [Command::Publish(..), Command::TransferObjects(..)]));
+
+        // Without gas coins the dry run pays with a mock gas coin
+        let gas_price = sui_client.read_api().get_reference_gas_price().await.unwrap();
+        let transaction_data =
+            TransactionData::new_with_gas_coins(TransactionKind::programmable(ptb), address, vec![], 100000000, gas_price);
         let result = sui_client
             .read_api()
-            .dry_run_transaction_block(transaction_bytes)
-            .await;
-        println!("result: {:?}", result);
+            .dry_run_transaction_block(transaction_data)
+            .await
+            .unwrap();
+        assert!(result.effects.status().is_ok(), "dry run failed: {:?}", result.effects.status());
 
+        fs::remove_dir_all(project_path).unwrap();
     }
 }
@@ -0,0 +1,83 @@
+// Template module renders package templates with `{{placeholder}}` syntax
+
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+
+/// Render a template, replacing every `{{name}}` with its value
+///
+/// Values are inserted as is and never rescanned, so a value containing `{{...}}`
+/// or another placeholder name cannot change the output.
+///
+/// # Arguments
+/// * `template` - The template text
+/// * `values` - The values using the placeholder name as key
+///
+/// # Returns
+/// * `Result<String>` - The rendered text or an error if a placeholder is unclosed or has no value
+pub fn render(template: &str, values: &HashMap<&str, String>) -> Result<String> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after
+            .find("}}")
+            .ok_or_else(|| anyhow!("Unclosed placeholder: {}", &rest[start..]))?;
+        let name = after[..end].trim();
+        let value = values
+            .get(name)
+            .ok_or_else(|| anyhow!("No value for placeholder: {}", name))?;
+        out.push_str(value);
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+
+    Ok(out)
+}
+
+/// Escape text for use inside a Move byte string literal `b"..."`
+///
+/// Quotes, backslashes and control characters are escaped and non-ASCII characters
+/// are written as `\xHH` UTF-8 bytes, so the literal holds exactly the bytes of `value`.
+///
+/// # Arguments
+/// * `value` - The text to escape
+///
+/// # Returns
+/// * `String` - The escaped text, without the surrounding `b"` and `"`
+pub fn escape_move_bytes(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'"' => out.push_str("\\\""),
+            b'\\' => out.push_str("\\\\"),
+            b'\n' => out.push_str("\\n"),
+            b'\r' => out.push_str("\\r"),
+            b'\t' => out.push_str("\\t"),
+            0x20..=0x7e => out.push(byte as char),
+            _ => out.push_str(&format!("\\x{:02x}", byte)),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let values = HashMap::from([("name", "{{symbol}} NAME".to_string()), ("symbol", "MTK".to_string())]);
+        assert_eq!(render("b\"{{ name }}\", b\"{{symbol}}\"", &values).unwrap(), "b\"{{symbol}} NAME\", b\"MTK\"");
+        assert!(render("{{missing}}", &values).is_err());
+        assert!(render("{{name", &values).is_err());
+    }
+
+    #[test]
+    fn test_escape_move_bytes() {
+        assert_eq!(escape_move_bytes("say \"hi\"\\n"), "say \\\"hi\\\"\\\\n");
+        assert_eq!(escape_move_bytes("line\nbreak"), "line\\nbreak");
+        assert_eq!(escape_move_bytes("café"), "caf\\xc3\\xa9");
+    }
+}
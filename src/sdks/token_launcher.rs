@@ -0,0 +1,358 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use shared_crypto::intent::Intent;
+use std::path::Path;
+use std::sync::Arc;
+use sui_json_rpc_types::{ObjectChange, SuiExecutionStatus, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponseOptions};
+use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
+use sui_sdk::SUI_COIN_TYPE;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::crypto::Signature;
+use sui_types::digests::TransactionDigest;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
+use sui_types::transaction::{Transaction, TransactionData};
+use sui_types::SUI_FRAMEWORK_ADDRESS;
+
+use crate::client::SuiQueryZClient;
+use crate::query::wallet::WalletQuery;
+use crate::utils::coin_selection::{self, CoinSelectionStrategy};
+use super::publish_contract::{publish_contract, ContractPublisher, TokenParams, UpgradeCapPolicy};
+
+/// Gas budget used to dry run the publish transaction, 1 SUI
+pub const DEFAULT_MAX_GAS_BUDGET: u64 = 1_000_000_000;
+
+/// Margin added to the gas cost measured by the dry run, in percent
+const GAS_BUDGET_MARGIN_PERCENT: u64 = 20;
+
+/// Trait for signing transactions
+///
+/// Implemented over a Sui keystore by `KeystoreSigner`; wallets or remote signers can provide their own implementation.
+#[async_trait]
+pub trait TransactionSigner: Send + Sync {
+    /// The address the signer signs for, which sends and pays for the transaction
+    fn address(&self) -> SuiAddress;
+
+    /// Sign transaction data
+    ///
+    /// # Arguments
+    /// * `tx_data` - The transaction data to sign
+    ///
+    /// # Returns
+    /// * `Result<Signature>` - The signature or an error
+    async fn sign(&self, tx_data: &TransactionData) -> Result<Signature>;
+}
+
+/// TransactionSigner backed by a Sui keystore, e.g. `~/.sui/sui_config/sui.keystore`
+pub struct KeystoreSigner {
+    keystore: Keystore,
+    address: SuiAddress,
+}
+
+impl KeystoreSigner {
+    /// Create a new KeystoreSigner
+    ///
+    /// # Arguments
+    /// * `keystore` - The keystore holding the key of `address`
+    /// * `address` - The address to sign for
+    ///
+    /// # Returns
+    /// * `Result<KeystoreSigner>` - A new signer instance or an error if the keystore has no key for the address
+    pub fn new(keystore: Keystore, address: SuiAddress) -> Result<Self> {
+        if !keystore.addresses().contains(&address) {
+            return Err(anyhow!("No key for address {} in keystore", address));
+        }
+        Ok(Self { keystore, address })
+    }
+
+    /// Create a new KeystoreSigner from a keystore file
+    ///
+    /// # Arguments
+    /// * `path` - The keystore file
+    /// * `address` - The address to sign for, defaults to the first address of the keystore
+    ///
+    /// # Returns
+    /// * `Result<KeystoreSigner>` - A new signer instance or an error
+    pub fn from_file(path: &Path, address: Option<SuiAddress>) -> Result<Self> {
+        let keystore = Keystore::File(FileBasedKeystore::new(&path.to_path_buf())?);
+        let address = match address {
+            Some(address) => address,
+            None => *keystore
+                .addresses()
+                .first()
+                .ok_or_else(|| anyhow!("Keystore is empty, path: {}", path.display()))?,
+        };
+        Self::new(keystore, address)
+    }
+}
+
+#[async_trait]
+impl TransactionSigner for KeystoreSigner {
+    fn address(&self) -> SuiAddress {
+        self.address
+    }
+
+    async fn sign(&self, tx_data: &TransactionData) -> Result<Signature> {
+        Ok(self.keystore.sign_secure(&self.address, tx_data, Intent::sui_transaction())?)
+    }
+}
+
+/// Represents a coin created by `TokenLauncher`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchedToken {
+    /// The digest of the publish transaction
+    pub digest: TransactionDigest,
+    /// The ID of the published package
+    pub package_id: ObjectID,
+    /// The coin type (e.g., "0x...::mtk::MTK")
+    pub coin_type: String,
+    /// The ID of the `TreasuryCap`
+    pub treasury_cap_id: ObjectID,
+    /// The ID of the frozen `CoinMetadata`
+    pub coin_metadata_id: ObjectID,
+    /// The ID of the `UpgradeCap`, `None` if the package was made immutable
+    pub upgrade_cap_id: Option<ObjectID>,
+}
+
+/// TokenLauncher renders, compiles, publishes and parses a new coin in one call
+pub struct TokenLauncher {
+    /// SUI client
+    client: Arc<SuiQueryZClient>,
+    /// Signer sending and paying for the publish transaction
+    signer: Arc<dyn TransactionSigner>,
+    /// Publisher rendering the coin package
+    publisher: ContractPublisher,
+    /// What to do with the `UpgradeCap`, defaults to transferring it to the signer
+    /// Never `UpgradeCapPolicy::Return`, as no later command could use the cap
+    upgrade_cap_policy: Option<UpgradeCapPolicy>,
+    /// Gas budget used for the dry run and upper bound of the final budget
+    max_gas_budget: u64,
+}
+
+impl TokenLauncher {
+    /// Create a new TokenLauncher writing packages to a temporary directory
+    ///
+    /// # Arguments
+    /// * `client` - SUI client instance
+    /// * `signer` - Signer sending and paying for the publish transaction
+    ///
+    /// # Returns
+    /// * `TokenLauncher` - A new launcher instance
+    pub fn new(client: Arc<SuiQueryZClient>, signer: Arc<dyn TransactionSigner>) -> Self {
+        let output_path = std::env::temp_dir().join("queryz_tokens");
+        Self {
+            client,
+            signer,
+            publisher: ContractPublisher::new(&output_path.to_string_lossy()),
+            upgrade_cap_policy: None,
+            max_gas_budget: DEFAULT_MAX_GAS_BUDGET,
+        }
+    }
+
+    /// Set the publisher rendering the coin package, e.g. to change its output directory or template
+    pub fn with_publisher(mut self, publisher: ContractPublisher) -> Self {
+        self.publisher = publisher;
+        self
+    }
+
+    /// Transfer the `UpgradeCap` of the coin package to an address instead of the signer
+    pub fn with_upgrade_cap_recipient(mut self, recipient: SuiAddress) -> Self {
+        self.upgrade_cap_policy = Some(UpgradeCapPolicy::Transfer(recipient));
+        self
+    }
+
+    /// Make the coin package immutable instead of keeping its `UpgradeCap`
+    pub fn with_immutable_package(mut self) -> Self {
+        self.upgrade_cap_policy = Some(UpgradeCapPolicy::Immutable);
+        self
+    }
+
+    /// Set the gas budget used for the dry run, which also bounds the final budget
+    ///
+    /// The dry run uses the sender's SUI balance instead when it is lower.
+    pub fn with_max_gas_budget(mut self, max_gas_budget: u64) -> Self {
+        self.max_gas_budget = max_gas_budget;
+        self
+    }
+
+    /// Launch a new coin
+    ///
+    /// # Arguments
+    /// * `params` - The parameters of the coin
+    ///
+    /// # Returns
+    /// * `Result<LaunchedToken>` - The created objects or an error if any step fails
+    pub async fn launch(&self, params: TokenParams) -> Result<LaunchedToken> {
+        let sender = self.signer.address();
+
+        // Render and compile the package
+        let project_name = self.publisher.publish_token(params)?;
+        let project_path = self.publisher.output_path().join(project_name);
+        let project_path_str = project_path
+            .to_str()
+            .ok_or_else(|| anyhow!("Invalid package path: {}", project_path.display()))?;
+
+        let upgrade_cap_policy = self.upgrade_cap_policy.unwrap_or(UpgradeCapPolicy::Transfer(sender));
+        let mut ptb = ProgrammableTransactionBuilder::new();
+        publish_contract(&mut ptb, project_path_str, upgrade_cap_policy)?;
+        let pt = ptb.finish();
+
+        // Estimate the budget with a dry run
+        let gas_price = self.client.sui_client().read_api().get_reference_gas_price().await?;
+        let gas_coins = WalletQuery::new(self.client.clone())
+            .get_coin_objects(&sender.to_string(), SUI_COIN_TYPE)
+            .await?;
+        // Wallets holding less than the max budget dry run with all they have
+        let balance = gas_coins.iter().map(|coin| coin.balance).fold(0u64, u64::saturating_add);
+        let dry_run_budget = self.max_gas_budget.min(balance);
+        let selection = coin_selection::select_coins(&gas_coins, dry_run_budget, CoinSelectionStrategy::FewestObjects)?;
+        let gas_payment = selection.coins.iter().map(|coin| coin.object_ref()).collect();
+        let dry_run_data = TransactionData::new_programmable(sender, gas_payment, pt.clone(), dry_run_budget, gas_price);
+
+        let dry_run = self.client.sui_client().read_api().dry_run_transaction_block(dry_run_data).await?;
+        if let SuiExecutionStatus::Failure { error } = dry_run.effects.status() {
+            return Err(anyhow!("Dry run of the publish transaction failed: {}", error));
+        }
+        let gas_summary = dry_run.effects.gas_cost_summary();
+        let gas_cost = gas_summary.computation_cost + gas_summary.storage_cost;
+        let gas_budget = (gas_cost + gas_cost * GAS_BUDGET_MARGIN_PERCENT / 100).min(dry_run_budget);
+
+        // Sign and execute
+        let selection = coin_selection::select_coins(&gas_coins, gas_budget, CoinSelectionStrategy::FewestObjects)?;
+        let gas_payment = selection.coins.iter().map(|coin| coin.object_ref()).collect();
+        let tx_data = TransactionData::new_programmable(sender, gas_payment, pt, gas_budget, gas_price);
+        let signature = self.signer.sign(&tx_data).await?;
+
+        let response = self
+            .client
+            .sui_client()
+            .quorum_driver_api()
+            .execute_transaction_block(
+                Transaction::from_data(tx_data, vec![signature]),
+                SuiTransactionBlockResponseOptions::new().with_effects().with_object_changes(),
+                Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+            )
+            .await?;
+
+        if let Some(SuiExecutionStatus::Failure { error }) = response.effects.as_ref().map(|effects| effects.status()) {
+            return Err(anyhow!("Publish transaction {} failed: {}", response.digest, error));
+        }
+        let object_changes = response
+            .object_changes
+            .ok_or_else(|| anyhow!("Publish transaction {} has no object changes", response.digest))?;
+
+        parse_launched_token(response.digest, &object_changes)
+    }
+}
+
+/// Extract the created package, caps and metadata from the object changes of a publish transaction
+fn parse_launched_token(digest: TransactionDigest, object_changes: &[ObjectChange]) -> Result<LaunchedToken> {
+    let mut package_id = None;
+    let mut coin_type = None;
+    let mut treasury_cap_id = None;
+    let mut coin_metadata_id = None;
+    let mut upgrade_cap_id = None;
+
+    for change in object_changes {
+        match change {
+            ObjectChange::Published { package_id: id, .. } => package_id = Some(*id),
+            ObjectChange::Created { object_type, object_id, .. } if object_type.address == SUI_FRAMEWORK_ADDRESS => {
+                match (object_type.module.as_str(), object_type.name.as_str()) {
+                    ("coin", "TreasuryCap") => {
+                        treasury_cap_id = Some(*object_id);
+                        coin_type = object_type.type_params.first().map(|tag| tag.to_canonical_string(true));
+                    }
+                    ("coin", "CoinMetadata") => coin_metadata_id = Some(*object_id),
+                    ("package", "UpgradeCap") => upgrade_cap_id = Some(*object_id),
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(LaunchedToken {
+        digest,
+        package_id: package_id.ok_or_else(|| anyhow!("No package published by transaction {}", digest))?,
+        coin_type: coin_type.ok_or_else(|| anyhow!("No TreasuryCap created by transaction {}", digest))?,
+        treasury_cap_id: treasury_cap_id.ok_or_else(|| anyhow!("No TreasuryCap created by transaction {}", digest))?,
+        coin_metadata_id: coin_metadata_id.ok_or_else(|| anyhow!("No CoinMetadata created by transaction {}", digest))?,
+        upgrade_cap_id,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_core_types::language_storage::StructTag;
+    use sui_types::base_types::SequenceNumber;
+    use sui_types::digests::ObjectDigest;
+    use sui_types::object::Owner;
+
+    #[test]
+    fn test_parse_launched_token() {
+        let sender = SuiAddress::random_for_testing_only();
+        let package_id = ObjectID::random();
+        let coin_tag: StructTag = format!("{}::mtk::MTK", package_id).parse().unwrap();
+        let created = |object_type: &str| ObjectChange::Created {
+            sender,
+            owner: Owner::AddressOwner(sender),
+            object_type: object_type.parse().unwrap(),
+            object_id: ObjectID::random(),
+            version: SequenceNumber::from_u64(1),
+            digest: ObjectDigest::random(),
+        };
+        let coin_type = coin_tag.to_canonical_string(true);
+        let changes = vec![
+            ObjectChange::Published {
+                package_id,
+                version: SequenceNumber::from_u64(1),
+                digest: ObjectDigest::random(),
+                modules: vec!["mtk".to_string()],
+            },
+            created(&format!("0x2::coin::TreasuryCap<{}>", coin_type)),
+            created(&format!("0x2::coin::CoinMetadata<{}>", coin_type)),
+            created("0x2::package::UpgradeCap"),
+        ];
+
+        let launched = parse_launched_token(TransactionDigest::random(), &changes).unwrap();
+        assert_eq!(launched.package_id, package_id);
+        assert_eq!(launched.coin_type, coin_type);
+        assert!(launched.upgrade_cap_id.is_some());
+
+        // An immutable package has no UpgradeCap, but a publish without a TreasuryCap is not a coin
+        assert!(parse_launched_token(TransactionDigest::random(), &changes[..3]).unwrap().upgrade_cap_id.is_none());
+        let without_treasury_cap = [changes[0].clone(), changes[2].clone()];
+        assert!(parse_launched_token(TransactionDigest::random(), &without_treasury_cap).is_err());
+    }
+
+    /// Needs a local network (`sui start --with-faucet`) and a funded address in the default keystore
+    #[tokio::test]
+    #[ignore]
+    async fn test_launch_token_on_localnet() {
+        let client = Arc::new(SuiQueryZClient::new("http://127.0.0.1:9000").await.unwrap());
+        let keystore_path = Path::new(&std::env::var("HOME").unwrap()).join(".sui/sui_config/sui.keystore");
+        let signer = Arc::new(KeystoreSigner::from_file(&keystore_path, None).unwrap());
+        let address = signer.address().to_string();
+
+        let params = TokenParams {
+            name: "Launch Token".to_string(),
+            symbol: "LAUNCH".to_string(),
+            decimals: 6,
+            description: "Token launched by the queryz test suite".to_string(),
+            initial_supply: 1_000_000_000,
+            freeze_authority: address.clone(),
+            mint_authority: address,
+            url: String::new(),
+        };
+
+        let launched = TokenLauncher::new(client.clone(), signer).launch(params).await.unwrap();
+        assert!(launched.coin_type.ends_with("::launch::LAUNCH"));
+        assert!(launched.upgrade_cap_id.is_some());
+
+        let metadata = client.get_coin_metadata(&launched.coin_type).await.unwrap();
+        assert_eq!(metadata.symbol, "LAUNCH");
+        assert_eq!(metadata.decimals, 6);
+    }
+}
@@ -1,15 +1,119 @@
 use serde::{Deserialize, Serialize};
+use sui_json_rpc_types::SuiCoinMetadata;
 use sui_types::base_types::ObjectID;
 
+use crate::core_clients::token_registry::TokenEntry;
+use crate::types::move_value::MoveValue;
+
+/// Where the symbol and decimals of a balance entry came from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MetadataSource {
+    /// The coin's on-chain `CoinMetadata` object
+    #[default]
+    OnChain,
+    /// A token registry entry, either of the coin metadata client or of a `MissingMetadataPolicy::Registry`
+    Registry,
+    /// No metadata was found; the balance is the raw amount
+    Missing,
+}
+
 /// Represents a balance entry in a Sui Bag
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Balancez {
     /// The coin type (e.g., "SUI")
     pub symbol: String,
     /// The coin type (e.g., "0x2::sui::SUI")
+    #[serde(default)]
     pub coin_type: String,
-    /// The balance amount
+    /// The balance amount, the unscaled raw amount when `is_raw` is true
     pub balance: f64,
+    /// The balance in the coin's smallest unit
+    #[serde(default)]
+    pub raw_balance: u128,
+    /// The coin's decimals, `None` when the coin has no metadata
+    #[serde(default)]
+    pub decimals: Option<u8>,
+    /// Where the symbol and decimals came from
+    #[serde(default)]
+    pub metadata_source: MetadataSource,
+    /// Whether the symbol and decimals come from a verified token registry entry
+    #[serde(default)]
+    pub verified: bool,
+    /// Whether the holder cannot use the balance because it is on the coin's deny list
+    ///
+    /// Only set by queries with deny list checks enabled.
+    #[serde(default)]
+    pub frozen: bool,
+}
+
+impl Balancez {
+    /// Create a balance entry scaled by the coin's decimals
+    ///
+    /// # Arguments
+    /// * `coin_type` - The coin type
+    /// * `raw_balance` - The balance in the coin's smallest unit
+    /// * `metadata` - The metadata of the coin
+    /// * `metadata_source` - Where the metadata came from
+    ///
+    /// # Returns
+    /// * `Balancez` - The balance entry
+    pub fn from_metadata(coin_type: String, raw_balance: u128, metadata: &SuiCoinMetadata, metadata_source: MetadataSource) -> Self {
+        Self {
+            symbol: metadata.symbol.clone(),
+            coin_type,
+            balance: raw_balance as f64 / 10.0_f64.powi(metadata.decimals as i32),
+            raw_balance,
+            decimals: Some(metadata.decimals),
+            metadata_source,
+            verified: false,
+            frozen: false,
+        }
+    }
+
+    /// Create a balance entry for a coin without metadata
+    ///
+    /// The coin type is used as symbol and the balance is the unscaled raw amount.
+    ///
+    /// # Arguments
+    /// * `coin_type` - The coin type
+    /// * `raw_balance` - The balance in the coin's smallest unit
+    ///
+    /// # Returns
+    /// * `Balancez` - The balance entry
+    pub fn without_metadata(coin_type: String, raw_balance: u128) -> Self {
+        Self {
+            symbol: coin_type.clone(),
+            coin_type,
+            balance: raw_balance as f64,
+            raw_balance,
+            decimals: None,
+            metadata_source: MetadataSource::Missing,
+            verified: false,
+            frozen: false,
+        }
+    }
+
+    /// Check whether the balance is the unscaled raw amount of a coin without metadata
+    ///
+    /// Raw amounts cannot be added to or compared with scaled balances.
+    pub fn is_raw(&self) -> bool {
+        self.metadata_source == MetadataSource::Missing
+    }
+
+    /// Mark the entry as coming from a token registry entry
+    ///
+    /// # Arguments
+    /// * `entry` - The registry entry of the coin, if any
+    ///
+    /// # Returns
+    /// * `Balancez` - The updated balance entry
+    pub fn with_token_entry(mut self, entry: Option<&TokenEntry>) -> Self {
+        if let Some(entry) = entry {
+            self.metadata_source = MetadataSource::Registry;
+            self.verified = entry.verified;
+        }
+        self
+    }
 }
 
 /// Represents all balances in a Sui Bag
@@ -20,3 +124,36 @@ pub struct BagBalances {
     /// List of balance entries in the bag
     pub balances: Vec<Balancez>,
 } 
+
+/// An entry of a Bag, decoded without a Rust type
+#[derive(Debug, Clone, Serialize)]
+pub struct BagEntry {
+    /// The ID of the dynamic field object holding the entry
+    pub field_id: ObjectID,
+    /// The key of the entry
+    pub name: MoveValue,
+    /// The value of the entry
+    pub value: MoveValue,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_balance_without_new_fields_deserializes() {
+        let balance: Balancez = serde_json::from_str(r#"{"symbol": "SUI", "balance": 1.5}"#).unwrap();
+        assert_eq!(balance.symbol, "SUI");
+        assert_eq!(balance.metadata_source, MetadataSource::OnChain);
+        assert!(!balance.is_raw());
+        assert!(!balance.verified && !balance.frozen);
+    }
+
+    #[test]
+    fn test_balance_without_metadata_is_raw() {
+        let balance = Balancez::without_metadata("0x42::lp::LP".to_string(), 1_500);
+        assert!(balance.is_raw());
+        assert_eq!(balance.balance, 1_500.0);
+        assert_eq!(balance.decimals, None);
+    }
+}
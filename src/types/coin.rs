@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+use sui_json_rpc_types::SuiCoinMetadata;
+use sui_types::base_types::{ObjectID, SuiAddress};
+
+/// Who currently holds a capability object such as a `TreasuryCap`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CapOwnership {
+    /// Owned by an address, which can use the capability
+    AddressOwned(SuiAddress),
+    /// Owned by another object, e.g. stored as a dynamic field
+    ObjectOwned(SuiAddress),
+    /// A shared object, usable under the rules of its module
+    Shared,
+    /// Frozen, so the capability can never be used again
+    Frozen,
+    /// Wrapped inside another object, so its holder depends on that object's module
+    Wrapped,
+    /// Deleted, so the capability can never be used again
+    Burned,
+}
+
+impl CapOwnership {
+    /// Check whether the capability can never be used again
+    pub fn is_renounced(&self) -> bool {
+        matches!(self, CapOwnership::Frozen | CapOwnership::Burned)
+    }
+}
+
+/// A capability object created together with a coin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapInfo {
+    /// The ID of the capability object
+    pub object_id: ObjectID,
+    /// The type of the capability, e.g. "0x2::coin::TreasuryCap<0x2::sui::SUI>"
+    pub object_type: String,
+    /// Who currently holds the capability
+    pub ownership: CapOwnership,
+}
+
+/// Supply, treasury and regulation information of a coin type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoinInfo {
+    /// The coin type (e.g., "0x2::sui::SUI")
+    pub coin_type: String,
+    /// The on-chain coin metadata, `None` if the coin has none
+    pub metadata: Option<SuiCoinMetadata>,
+    /// The total supply in the coin's smallest unit, `None` if the `TreasuryCap` was not found, wrapped or deleted
+    pub total_supply: Option<u64>,
+    /// The `TreasuryCap` of the coin, `None` if it could not be found
+    pub treasury_cap: Option<CapInfo>,
+    /// Whether the coin is a regulated coin
+    pub regulated: bool,
+    /// The `DenyCap` or `DenyCapV2` of a regulated coin
+    pub deny_cap: Option<CapInfo>,
+}
+
+impl CoinInfo {
+    /// Check whether new coins can no longer be minted
+    ///
+    /// # Returns
+    /// * `bool` - True if the `TreasuryCap` is frozen or burned
+    pub fn is_supply_fixed(&self) -> bool {
+        self.treasury_cap.as_ref().is_some_and(|cap| cap.ownership.is_renounced())
+    }
+}
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+use sui_types::base_types::SuiAddress;
+
+/// Whether an address is on the deny list of a regulated coin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddressDenyStatus {
+    /// The address
+    pub address: SuiAddress,
+    /// Whether the address is denied in the current epoch
+    pub denied_current_epoch: bool,
+    /// Whether the address will be denied in the next epoch
+    pub denied_next_epoch: bool,
+}
+
+/// Deny list state of a regulated coin for a set of addresses
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoinDenyStatus {
+    /// The coin type (e.g., "0x2::sui::SUI")
+    pub coin_type: String,
+    /// The epoch the state was read in
+    pub epoch: u64,
+    /// Whether the coin has a deny list config, i.e. is a regulated coin with a `DenyCapV2`
+    pub regulated: bool,
+    /// Whether all transfers of the coin are paused in the current epoch
+    pub paused_current_epoch: bool,
+    /// Whether all transfers of the coin will be paused in the next epoch
+    pub paused_next_epoch: bool,
+    /// The status of each requested address
+    pub addresses: Vec<AddressDenyStatus>,
+}
+
+impl CoinDenyStatus {
+    /// Check whether an address cannot use its balance of the coin in the current epoch
+    ///
+    /// # Arguments
+    /// * `address` - The address to check
+    ///
+    /// # Returns
+    /// * `bool` - True if the coin is paused or the address is denied
+    pub fn is_frozen(&self, address: SuiAddress) -> bool {
+        self.paused_current_epoch
+            || self.addresses.iter().any(|status| status.address == address && status.denied_current_epoch)
+    }
+}
@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use sui_types::base_types::{ObjectID, SuiAddress};
+
+/// Represents an object rendered through its on-chain `Display<T>` template
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ObjectDisplay {
+    /// The rendered `name` field
+    pub name: Option<String>,
+    /// The rendered `description` field
+    pub description: Option<String>,
+    /// The rendered `image_url` field
+    pub image_url: Option<String>,
+    /// All rendered fields, including the ones above
+    pub fields: BTreeMap<String, String>,
+}
+
+impl ObjectDisplay {
+    /// Create an ObjectDisplay from the rendered display fields
+    ///
+    /// # Arguments
+    /// * `fields` - The rendered fields of the display template
+    ///
+    /// # Returns
+    /// * `ObjectDisplay` - The display with the well-known fields extracted
+    pub fn from_fields(fields: BTreeMap<String, String>) -> Self {
+        Self {
+            name: fields.get("name").cloned(),
+            description: fields.get("description").cloned(),
+            image_url: fields.get("image_url").cloned(),
+            fields,
+        }
+    }
+}
+
+/// Represents a non-coin object in an inventory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InventoryItem {
+    /// The ID of the object
+    pub object_id: ObjectID,
+    /// The version of the object
+    pub version: u64,
+    /// The struct type of the object (e.g., "0x...::nft::Nft")
+    pub object_type: String,
+    /// The rendered display, if the type has a `Display<T>` template
+    pub display: Option<ObjectDisplay>,
+}
+
+/// Represents all inventory items of the same struct type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InventoryCollection {
+    /// The struct type shared by all items
+    pub object_type: String,
+    /// The items of this type
+    pub items: Vec<InventoryItem>,
+}
+
+/// Represents the non-coin objects of an owner, grouped by struct type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectInventory {
+    /// The owner of the objects
+    pub owner: SuiAddress,
+    /// Items grouped by struct type, ordered by type
+    pub collections: Vec<InventoryCollection>,
+}
+
+impl ObjectInventory {
+    /// Group inventory items by struct type
+    ///
+    /// # Arguments
+    /// * `owner` - The owner of the items
+    /// * `items` - The items to group
+    ///
+    /// # Returns
+    /// * `ObjectInventory` - The grouped inventory
+    pub fn from_items(owner: SuiAddress, items: Vec<InventoryItem>) -> Self {
+        let mut grouped: BTreeMap<String, Vec<InventoryItem>> = BTreeMap::new();
+        for item in items {
+            grouped.entry(item.object_type.clone()).or_default().push(item);
+        }
+
+        let collections = grouped
+            .into_iter()
+            .map(|(object_type, items)| InventoryCollection { object_type, items })
+            .collect();
+
+        Self { owner, collections }
+    }
+}
@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+use sui_types::base_types::{ObjectID, SuiAddress};
+
+use crate::types::inventory::{InventoryItem, ObjectInventory};
+
+/// Represents a kiosk listing of an item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KioskListing {
+    /// The listing price in MIST
+    pub price: u64,
+    /// Whether the item is listed exclusively through a purchase cap
+    pub is_exclusive: bool,
+}
+
+/// Represents an item placed in a kiosk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KioskItem {
+    /// The item in the same shape as wallet inventory items
+    pub item: InventoryItem,
+    /// Whether the item is locked and can only leave the kiosk by being sold
+    pub is_locked: bool,
+    /// The listing of the item, if it is for sale
+    pub listing: Option<KioskListing>,
+}
+
+/// Represents the contents of a single kiosk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KioskContents {
+    /// The ID of the kiosk object
+    pub kiosk_id: ObjectID,
+    /// The ID of the cap proving ownership of the kiosk
+    pub owner_cap_id: ObjectID,
+    /// Whether the kiosk is owned through a `PersonalKioskCap`
+    pub is_personal: bool,
+    /// The items placed in the kiosk
+    pub items: Vec<KioskItem>,
+}
+
+/// Represents all kiosks owned by an address
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddressKiosks {
+    /// The owner of the kiosks
+    pub owner: SuiAddress,
+    /// The kiosks of the owner
+    pub kiosks: Vec<KioskContents>,
+}
+
+impl AddressKiosks {
+    /// Group the items of all kiosks by struct type
+    ///
+    /// # Returns
+    /// * `ObjectInventory` - The kiosk items in the same shape as a wallet inventory
+    pub fn to_inventory(&self) -> ObjectInventory {
+        let items = self.kiosks
+            .iter()
+            .flat_map(|kiosk| kiosk.items.iter().map(|item| item.item.clone()))
+            .collect();
+        ObjectInventory::from_items(self.owner, items)
+    }
+}
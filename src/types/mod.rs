@@ -1,2 +1,10 @@
 pub mod type_name;
 pub mod bag;
+pub mod wallet;
+pub mod staking;
+pub mod inventory;
+pub mod kiosk;
+pub mod coin;
+pub mod deny_list;
+pub mod move_value;
+pub mod move_type;
@@ -0,0 +1,333 @@
+use anyhow::{anyhow, Result};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::u256::U256;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::dynamic_field::Field;
+use sui_types::id::{ID, UID};
+use sui_types::{MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS};
+
+pub use move_core_types::language_storage::StructTag;
+pub use queryz_derive::MoveStruct;
+pub use sui_types::TypeTag;
+
+pub use crate::types::move_value::{MoveStructLayout, MoveTypeLayout};
+use crate::types::type_name::TypeName;
+
+/// A Rust type with a known Move type and BCS layout
+pub trait MoveType {
+    /// The Move type
+    fn type_tag() -> TypeTag;
+
+    /// The BCS layout, as built from the on-chain module by `MoveLayoutClient`
+    fn layout() -> MoveTypeLayout;
+}
+
+/// A Rust mirror of a Move struct, usually implemented with `#[derive(MoveStruct)]`
+pub trait MoveStruct: MoveType {
+    /// The Move struct type
+    fn struct_tag() -> StructTag;
+
+    /// The BCS layout of the struct
+    fn struct_layout() -> MoveStructLayout;
+}
+
+/// Build a struct tag, used by the code generated by `#[derive(MoveStruct)]`
+///
+/// # Panics
+/// If the address or identifiers are invalid; the derive macro checks the address at compile time.
+pub fn struct_tag(address: &str, module: &str, name: &str, type_params: Vec<TypeTag>) -> StructTag {
+    StructTag {
+        address: AccountAddress::from_hex_literal(address).expect("invalid Move struct address"),
+        module: Identifier::new(module).expect("invalid Move module name"),
+        name: Identifier::new(name).expect("invalid Move struct name"),
+        type_params,
+    }
+}
+
+/// Check that the layout of a Rust type matches the on-chain layout of its Move type
+///
+/// # Arguments
+/// * `on_chain` - The layout built from the on-chain module
+/// * `rust` - The layout of the Rust type
+/// * `path` - Where the layouts are compared, used in error messages
+///
+/// # Returns
+/// * `Result<()>` - An error describing the first difference
+pub fn check_layout(on_chain: &MoveTypeLayout, rust: &MoveTypeLayout, path: &str) -> Result<()> {
+    match (on_chain, rust) {
+        (MoveTypeLayout::Vector(on_chain), MoveTypeLayout::Vector(rust))
+        | (MoveTypeLayout::Option(on_chain), MoveTypeLayout::Option(rust)) => check_layout(on_chain, rust, path),
+        (MoveTypeLayout::Struct(on_chain), MoveTypeLayout::Struct(rust)) => {
+            if on_chain.type_ != rust.type_ {
+                return Err(anyhow!(
+                    "{}: on-chain type is {} but the Rust type is {}",
+                    path,
+                    on_chain.type_.to_canonical_string(true),
+                    rust.type_.to_canonical_string(true)
+                ));
+            }
+            if on_chain.fields.len() != rust.fields.len() {
+                return Err(anyhow!(
+                    "{}: on-chain struct has {} fields but the Rust struct has {}",
+                    path,
+                    on_chain.fields.len(),
+                    rust.fields.len()
+                ));
+            }
+            for (index, ((on_chain_name, on_chain_field), (rust_name, rust_field))) in on_chain.fields.iter().zip(&rust.fields).enumerate() {
+                if on_chain_name != rust_name {
+                    return Err(anyhow!(
+                        "{}: field {} is `{}` on chain but `{}` in Rust",
+                        path,
+                        index,
+                        on_chain_name,
+                        rust_name
+                    ));
+                }
+                check_layout(on_chain_field, rust_field, &format!("{}.{}", path, on_chain_name))?;
+            }
+            Ok(())
+        }
+        (on_chain, rust) if on_chain == rust => Ok(()),
+        (on_chain, rust) => Err(anyhow!("{}: on-chain layout is {:?} but the Rust layout is {:?}", path, on_chain, rust)),
+    }
+}
+
+macro_rules! impl_primitive_move_type {
+    ($($ty:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl MoveType for $ty {
+                fn type_tag() -> TypeTag {
+                    TypeTag::$variant
+                }
+
+                fn layout() -> MoveTypeLayout {
+                    MoveTypeLayout::$variant
+                }
+            }
+        )*
+    };
+}
+
+impl_primitive_move_type! {
+    bool => Bool,
+    u8 => U8,
+    u16 => U16,
+    u32 => U32,
+    u64 => U64,
+    u128 => U128,
+    U256 => U256,
+    SuiAddress => Address,
+}
+
+impl<T: MoveType> MoveType for Vec<T> {
+    fn type_tag() -> TypeTag {
+        TypeTag::Vector(Box::new(T::type_tag()))
+    }
+
+    fn layout() -> MoveTypeLayout {
+        MoveTypeLayout::Vector(Box::new(T::layout()))
+    }
+}
+
+impl<T: MoveType> MoveType for Option<T> {
+    fn type_tag() -> TypeTag {
+        TypeTag::Struct(Box::new(StructTag {
+            address: MOVE_STDLIB_ADDRESS,
+            module: Identifier::new("option").unwrap(),
+            name: Identifier::new("Option").unwrap(),
+            type_params: vec![T::type_tag()],
+        }))
+    }
+
+    fn layout() -> MoveTypeLayout {
+        MoveTypeLayout::Option(Box::new(T::layout()))
+    }
+}
+
+/// `String` mirrors `0x1::string::String`
+impl MoveType for String {
+    fn type_tag() -> TypeTag {
+        TypeTag::Struct(Box::new(struct_tag("0x1", "string", "String", vec![])))
+    }
+
+    fn layout() -> MoveTypeLayout {
+        MoveTypeLayout::String
+    }
+}
+
+/// Mirror of `0x1::ascii::String`, which has the same BCS layout as `0x1::string::String`
+/// but a different type, so it must be told apart in type tags
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AsciiString(pub String);
+
+impl AsciiString {
+    /// The string value
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for AsciiString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<AsciiString> for String {
+    fn from(value: AsciiString) -> Self {
+        value.0
+    }
+}
+
+impl MoveType for AsciiString {
+    fn type_tag() -> TypeTag {
+        TypeTag::Struct(Box::new(struct_tag("0x1", "ascii", "String", vec![])))
+    }
+
+    fn layout() -> MoveTypeLayout {
+        MoveTypeLayout::String
+    }
+}
+
+/// `ObjectID` mirrors `0x2::object::ID`, as `ID` fields are the usual way objects refer to each other
+impl MoveType for ObjectID {
+    fn type_tag() -> TypeTag {
+        <ID as MoveType>::type_tag()
+    }
+
+    fn layout() -> MoveTypeLayout {
+        MoveTypeLayout::Id
+    }
+}
+
+impl MoveType for ID {
+    fn type_tag() -> TypeTag {
+        TypeTag::Struct(Box::new(struct_tag("0x2", "object", "ID", vec![])))
+    }
+
+    fn layout() -> MoveTypeLayout {
+        MoveTypeLayout::Id
+    }
+}
+
+impl MoveType for UID {
+    fn type_tag() -> TypeTag {
+        TypeTag::Struct(Box::new(struct_tag("0x2", "object", "UID", vec![])))
+    }
+
+    fn layout() -> MoveTypeLayout {
+        MoveTypeLayout::Id
+    }
+}
+
+impl MoveType for TypeName {
+    fn type_tag() -> TypeTag {
+        TypeTag::Struct(Box::new(<Self as MoveStruct>::struct_tag()))
+    }
+
+    fn layout() -> MoveTypeLayout {
+        MoveTypeLayout::Struct(<Self as MoveStruct>::struct_layout())
+    }
+}
+
+impl MoveStruct for TypeName {
+    fn struct_tag() -> StructTag {
+        struct_tag("0x1", "type_name", "TypeName", vec![])
+    }
+
+    fn struct_layout() -> MoveStructLayout {
+        MoveStructLayout {
+            type_: Self::struct_tag(),
+            fields: vec![("name".to_string(), MoveTypeLayout::String)],
+        }
+    }
+}
+
+impl<N: MoveType, V: MoveType> MoveType for Field<N, V> {
+    fn type_tag() -> TypeTag {
+        TypeTag::Struct(Box::new(<Self as MoveStruct>::struct_tag()))
+    }
+
+    fn layout() -> MoveTypeLayout {
+        MoveTypeLayout::Struct(<Self as MoveStruct>::struct_layout())
+    }
+}
+
+/// `Field` mirrors `0x2::dynamic_field::Field`, the object holding each entry of a Bag or Table
+impl<N: MoveType, V: MoveType> MoveStruct for Field<N, V> {
+    fn struct_tag() -> StructTag {
+        StructTag {
+            address: SUI_FRAMEWORK_ADDRESS,
+            module: Identifier::new("dynamic_field").unwrap(),
+            name: Identifier::new("Field").unwrap(),
+            type_params: vec![N::type_tag(), V::type_tag()],
+        }
+    }
+
+    fn struct_layout() -> MoveStructLayout {
+        MoveStructLayout {
+            type_: Self::struct_tag(),
+            fields: vec![
+                ("id".to_string(), UID::layout()),
+                ("name".to_string(), N::layout()),
+                ("value".to_string(), V::layout()),
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use std::marker::PhantomData;
+
+    #[allow(dead_code)]
+    #[derive(Deserialize, MoveStruct)]
+    #[move_struct(address = "0x2", module = "balance", name = "Supply")]
+    struct Supply<T> {
+        value: u64,
+        phantom: PhantomData<T>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Deserialize, MoveStruct)]
+    #[move_struct(address = "0x42", module = "pool", name = "Pool")]
+    struct SwappedPool {
+        id: UID,
+        #[move_field(rename = "reserve_y")]
+        reserve_x: u64,
+        reserve_x_supply: Supply<SuiAddress>,
+    }
+
+    #[test]
+    fn test_derived_struct_tag_and_layout() {
+        let tag = Supply::<SuiAddress>::struct_tag();
+        assert_eq!(tag.to_canonical_string(true), format!("0x{:0>64}::balance::Supply<address>", "2"));
+        assert_eq!(
+            Supply::<SuiAddress>::struct_layout().fields,
+            vec![("value".to_string(), MoveTypeLayout::U64)]
+        );
+    }
+
+    #[test]
+    fn test_check_layout_reports_field_order() {
+        let mut on_chain = SwappedPool::struct_layout();
+        on_chain.fields.swap(1, 2);
+
+        let error = check_layout(&MoveTypeLayout::Struct(on_chain), &SwappedPool::layout(), "Pool").unwrap_err();
+        assert_eq!(error.to_string(), "Pool: field 1 is `reserve_x_supply` on chain but `reserve_y` in Rust");
+    }
+
+    #[test]
+    fn test_ascii_string_type_tag() {
+        assert_eq!(AsciiString::type_tag().to_canonical_string(true), format!("0x{:0>64}::ascii::String", "1"));
+        assert_ne!(AsciiString::type_tag(), String::type_tag());
+        assert_eq!(AsciiString::layout(), String::layout());
+    }
+}
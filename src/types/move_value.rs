@@ -0,0 +1,239 @@
+use anyhow::{anyhow, Result};
+use move_core_types::language_storage::StructTag;
+use move_core_types::u256::U256;
+use serde::{Serialize, Serializer};
+use serde_json::{json, Map, Value};
+use sui_types::base_types::{ObjectID, SuiAddress};
+
+/// A Move value decoded from BCS without a Rust type
+#[derive(Debug, Clone, PartialEq)]
+pub enum MoveValue {
+    Bool(bool),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    U256(U256),
+    Address(SuiAddress),
+    /// A `0x1::string::String` or `0x1::ascii::String`
+    String(String),
+    /// A `0x2::object::UID` or `0x2::object::ID`
+    Id(ObjectID),
+    /// A `0x1::option::Option<T>`
+    Option(Option<Box<MoveValue>>),
+    Vector(Vec<MoveValue>),
+    Struct(MoveStruct),
+}
+
+/// A decoded Move struct
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveStruct {
+    /// The type of the struct
+    pub type_: StructTag,
+    /// The fields in declaration order
+    pub fields: Vec<(String, MoveValue)>,
+}
+
+impl MoveStruct {
+    /// Get a field by name
+    pub fn field(&self, name: &str) -> Option<&MoveValue> {
+        self.fields.iter().find(|(field_name, _)| field_name == name).map(|(_, value)| value)
+    }
+}
+
+impl MoveValue {
+    /// Convert the value to JSON
+    ///
+    /// Integers wider than 64 bits are rendered as strings, as in the Sui RPC.
+    pub fn to_json(&self) -> Value {
+        match self {
+            MoveValue::Bool(v) => json!(v),
+            MoveValue::U8(v) => json!(v),
+            MoveValue::U16(v) => json!(v),
+            MoveValue::U32(v) => json!(v),
+            MoveValue::U64(v) => json!(v.to_string()),
+            MoveValue::U128(v) => json!(v.to_string()),
+            MoveValue::U256(v) => json!(v.to_string()),
+            MoveValue::Address(v) => json!(v.to_string()),
+            MoveValue::String(v) => json!(v),
+            MoveValue::Id(v) => json!(v.to_string()),
+            MoveValue::Option(v) => v.as_ref().map_or(Value::Null, |v| v.to_json()),
+            MoveValue::Vector(values) => Value::Array(values.iter().map(MoveValue::to_json).collect()),
+            MoveValue::Struct(move_struct) => {
+                let fields: Map<String, Value> = move_struct
+                    .fields
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.to_json()))
+                    .collect();
+                Value::Object(fields)
+            }
+        }
+    }
+}
+
+impl Serialize for MoveValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_json().serialize(serializer)
+    }
+}
+
+/// The BCS layout of a Move type
+#[derive(Debug, Clone, PartialEq)]
+pub enum MoveTypeLayout {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    U256,
+    Address,
+    /// A `0x1::string::String` or `0x1::ascii::String`, encoded as `vector<u8>`
+    String,
+    /// A `0x2::object::UID` or `0x2::object::ID`, encoded as an address
+    Id,
+    /// A `0x1::option::Option<T>`, encoded as a vector of at most one element
+    Option(Box<MoveTypeLayout>),
+    Vector(Box<MoveTypeLayout>),
+    Struct(MoveStructLayout),
+}
+
+/// The BCS layout of a Move struct
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveStructLayout {
+    /// The type of the struct
+    pub type_: StructTag,
+    /// The fields in declaration order
+    pub fields: Vec<(String, MoveTypeLayout)>,
+}
+
+impl MoveTypeLayout {
+    /// Decode BCS bytes with this layout
+    ///
+    /// # Arguments
+    /// * `bytes` - The BCS bytes of a single value
+    ///
+    /// # Returns
+    /// * `Result<MoveValue>` - The decoded value or an error if the bytes do not match the layout
+    pub fn decode(&self, bytes: &[u8]) -> Result<MoveValue> {
+        let mut reader = BcsReader { bytes, offset: 0 };
+        let value = reader.read_value(self)?;
+        if reader.offset != bytes.len() {
+            return Err(anyhow!("{} trailing bytes after decoding", bytes.len() - reader.offset));
+        }
+        Ok(value)
+    }
+}
+
+/// Reads values from BCS bytes
+struct BcsReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> BcsReader<'a> {
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self.offset
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| anyhow!("unexpected end of BCS bytes at offset {}", self.offset))?;
+        let bytes = &self.bytes[self.offset..end];
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        Ok(self.read_bytes(N)?.try_into()?)
+    }
+
+    /// Read a ULEB128 encoded length
+    fn read_len(&mut self) -> Result<usize> {
+        let mut value: u64 = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_bytes(1)?[0];
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(usize::try_from(value)?);
+            }
+        }
+        Err(anyhow!("invalid ULEB128 length at offset {}", self.offset))
+    }
+
+    fn read_value(&mut self, layout: &MoveTypeLayout) -> Result<MoveValue> {
+        let value = match layout {
+            MoveTypeLayout::Bool => match self.read_bytes(1)?[0] {
+                0 => MoveValue::Bool(false),
+                1 => MoveValue::Bool(true),
+                byte => return Err(anyhow!("invalid bool byte {}", byte)),
+            },
+            MoveTypeLayout::U8 => MoveValue::U8(self.read_bytes(1)?[0]),
+            MoveTypeLayout::U16 => MoveValue::U16(u16::from_le_bytes(self.read_array()?)),
+            MoveTypeLayout::U32 => MoveValue::U32(u32::from_le_bytes(self.read_array()?)),
+            MoveTypeLayout::U64 => MoveValue::U64(u64::from_le_bytes(self.read_array()?)),
+            MoveTypeLayout::U128 => MoveValue::U128(u128::from_le_bytes(self.read_array()?)),
+            MoveTypeLayout::U256 => MoveValue::U256(U256::from_le_bytes(&self.read_array()?)),
+            MoveTypeLayout::Address => MoveValue::Address(SuiAddress::from_bytes(self.read_array::<32>()?)?),
+            MoveTypeLayout::Id => MoveValue::Id(ObjectID::new(self.read_array()?)),
+            MoveTypeLayout::String => {
+                let len = self.read_len()?;
+                MoveValue::String(String::from_utf8(self.read_bytes(len)?.to_vec())?)
+            }
+            MoveTypeLayout::Option(inner) => match self.read_len()? {
+                0 => MoveValue::Option(None),
+                1 => MoveValue::Option(Some(Box::new(self.read_value(inner)?))),
+                len => return Err(anyhow!("invalid Option length {}", len)),
+            },
+            MoveTypeLayout::Vector(inner) => {
+                let len = self.read_len()?;
+                let mut values = Vec::with_capacity(len.min(self.bytes.len() - self.offset));
+                for _ in 0..len {
+                    values.push(self.read_value(inner)?);
+                }
+                MoveValue::Vector(values)
+            }
+            MoveTypeLayout::Struct(struct_layout) => {
+                let mut fields = Vec::with_capacity(struct_layout.fields.len());
+                for (name, field_layout) in &struct_layout.fields {
+                    fields.push((name.clone(), self.read_value(field_layout)?));
+                }
+                MoveValue::Struct(MoveStruct {
+                    type_: struct_layout.type_.clone(),
+                    fields,
+                })
+            }
+        };
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_types::parse_sui_struct_tag;
+
+    #[test]
+    fn test_decode_struct() {
+        let layout = MoveTypeLayout::Struct(MoveStructLayout {
+            type_: parse_sui_struct_tag("0x42::pool::Pool").unwrap(),
+            fields: vec![
+                ("id".to_string(), MoveTypeLayout::Id),
+                ("name".to_string(), MoveTypeLayout::String),
+                ("fee".to_string(), MoveTypeLayout::Option(Box::new(MoveTypeLayout::U64))),
+                ("reserves".to_string(), MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U128))),
+            ],
+        });
+        let id = ObjectID::random();
+        let bytes = bcs::to_bytes(&(id, "SUI/USDC", Some(30u64), vec![1u128, 2u128])).unwrap();
+
+        let MoveValue::Struct(pool) = layout.decode(&bytes).unwrap() else {
+            panic!("expected a struct");
+        };
+        assert_eq!(pool.field("id"), Some(&MoveValue::Id(id)));
+        assert_eq!(pool.field("name"), Some(&MoveValue::String("SUI/USDC".to_string())));
+        assert_eq!(pool.field("fee"), Some(&MoveValue::Option(Some(Box::new(MoveValue::U64(30))))));
+        assert_eq!(MoveValue::Struct(pool).to_json()["reserves"], json!(["1", "2"]));
+
+        assert!(layout.decode(&bytes[..bytes.len() - 1]).is_err());
+    }
+}
@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use sui_types::base_types::{ObjectID, SuiAddress};
+
+/// Status of a staked SUI position
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StakePositionStatus {
+    /// The stake becomes active at the start of its activation epoch
+    Pending,
+    /// The stake is earning rewards
+    Active,
+    /// The stake has been withdrawn
+    Unstaked,
+}
+
+/// Represents a single `StakedSui` object
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StakedSuiPosition {
+    /// The ID of the `StakedSui` object
+    pub staked_sui_id: ObjectID,
+    /// The staked principal in MIST
+    pub principal: u64,
+    /// The epoch in which the stake was requested
+    pub stake_request_epoch: u64,
+    /// The epoch from which the stake earns rewards
+    pub stake_active_epoch: u64,
+    /// Rewards accrued so far in MIST, as estimated by the fullnode
+    pub estimated_reward: u64,
+    /// The status of the stake
+    pub status: StakePositionStatus,
+}
+
+/// Represents all stakes of a wallet with a single validator
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidatorStakes {
+    /// The address of the validator
+    pub validator_address: SuiAddress,
+    /// The ID of the validator's staking pool
+    pub staking_pool: ObjectID,
+    /// The `StakedSui` objects delegated to this validator
+    pub positions: Vec<StakedSuiPosition>,
+    /// Sum of the principal of all positions in MIST
+    pub total_principal: u64,
+    /// Sum of the estimated rewards of all positions in MIST
+    pub total_estimated_reward: u64,
+}
+
+/// Represents all staked SUI of a wallet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletStakes {
+    /// The wallet address
+    pub address: SuiAddress,
+    /// Stakes grouped by validator
+    pub validators: Vec<ValidatorStakes>,
+    /// Sum of the principal of all positions in MIST
+    pub total_principal: u64,
+    /// Sum of the estimated rewards of all positions in MIST
+    pub total_estimated_reward: u64,
+}
+
+/// Represents the total holdings of a wallet including staked SUI
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletNetWorth {
+    /// The wallet address
+    pub address: SuiAddress,
+    /// Liquid SUI balance in MIST
+    pub liquid_sui: u64,
+    /// Staked SUI principal in MIST
+    pub staked_principal: u64,
+    /// Estimated staking rewards in MIST
+    pub estimated_rewards: u64,
+    /// Balances by symbol, with staked principal and rewards added to SUI
+    ///
+    /// Coins without metadata are left out; their raw amounts cannot be mixed with scaled balances.
+    pub balances: HashMap<String, f64>,
+}
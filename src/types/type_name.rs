@@ -1,17 +1,81 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::{fmt, str::FromStr};
-use sui_types::TypeTag;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::StructTag;
+use sui_types::{parse_sui_type_tag, TypeTag};
 
 use crate::utils::coin::format_coin_address;
 
-#[derive(Debug, Serialize, Deserialize, Clone, Eq, Default)]
+/// A Move type, mirroring `std::type_name::TypeName`
+///
+/// On chain the type is stored as a string without "0x" prefixes and with full
+/// length addresses, e.g. "00..02::sui::SUI" or "vector<u8>"; this is also how it is
+/// serialized. Parsing accepts short and prefixed addresses, including inside type params.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct TypeName {
-    pub name: String,
+    tag: TypeTag,
 }
 
 impl TypeName {
+    /// The address of the package defining the type, `None` if it is not a struct
+    pub fn address(&self) -> Option<AccountAddress> {
+        self.struct_tag().map(|tag| tag.address)
+    }
+
+    /// The module defining the type, `None` if it is not a struct
+    pub fn module(&self) -> Option<&str> {
+        self.struct_tag().map(|tag| tag.module.as_str())
+    }
+
+    /// The name of the type, `None` if it is not a struct
+    pub fn name(&self) -> Option<&str> {
+        self.struct_tag().map(|tag| tag.name.as_str())
+    }
+
+    /// The type params of the type, `None` if it is not a struct
+    pub fn type_params(&self) -> Option<&[TypeTag]> {
+        self.struct_tag().map(|tag| tag.type_params.as_slice())
+    }
+
+    /// The parsed struct tag, `None` if it is not a struct
+    pub fn struct_tag(&self) -> Option<&StructTag> {
+        match &self.tag {
+            TypeTag::Struct(tag) => Some(tag),
+            _ => None,
+        }
+    }
+
+    /// The parsed type
+    pub fn type_tag(&self) -> &TypeTag {
+        &self.tag
+    }
+
+    /// The type with full length "0x" prefixed addresses, e.g. "0x00..02::sui::SUI"
     pub fn into_string(&self) -> String {
-        format_coin_address(self.name.as_str())
+        self.tag.to_canonical_string(true)
+    }
+
+    /// The type in the on-chain `std::type_name::TypeName` format, e.g. "00..02::sui::SUI"
+    pub fn to_on_chain_string(&self) -> String {
+        self.tag.to_canonical_string(false)
+    }
+}
+
+impl From<TypeTag> for TypeName {
+    fn from(tag: TypeTag) -> Self {
+        Self { tag }
+    }
+}
+
+impl From<StructTag> for TypeName {
+    fn from(tag: StructTag) -> Self {
+        Self { tag: TypeTag::Struct(Box::new(tag)) }
+    }
+}
+
+impl From<TypeName> for TypeTag {
+    fn from(type_name: TypeName) -> Self {
+        type_name.tag
     }
 }
 
@@ -24,15 +88,61 @@ impl fmt::Display for TypeName {
 impl FromStr for TypeName {
     type Err = anyhow::Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let type_tag = TypeTag::from_str(s)?;
-        Ok(TypeName {
-            name: type_tag.to_string(),
-        })
+        // On-chain names have no "0x" prefixes, which the type parser requires
+        let tag = parse_sui_type_tag(&format_coin_address(s.trim()))?;
+        Ok(TypeName { tag })
+    }
+}
+
+impl Serialize for TypeName {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_on_chain_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for TypeName {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        TypeName::from_str(&name).map_err(serde::de::Error::custom)
     }
 }
 
-impl PartialEq for TypeName {
-    fn eq(&self, other: &Self) -> bool {
-        self.into_string().eq(&other.into_string())
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bcs_round_trip() {
+        let on_chain = format!("{:0>64}::coin::Coin<{:0>64}::sui::SUI>", "2", "2");
+        let bytes = bcs::to_bytes(&on_chain).unwrap();
+
+        let type_name: TypeName = bcs::from_bytes(&bytes).unwrap();
+        assert_eq!(type_name.module(), Some("coin"));
+        assert_eq!(type_name.name(), Some("Coin"));
+        assert_eq!(type_name.type_params().map(<[TypeTag]>::len), Some(1));
+        assert_eq!(bcs::to_bytes(&type_name).unwrap(), bytes);
+    }
+
+    #[test]
+    fn test_short_and_full_addresses_are_equal() {
+        let short: TypeName = "0x2::coin::Coin<0x2::sui::SUI>".parse().unwrap();
+        let full: TypeName = format!("0x{:0>64}::coin::Coin<0x{:0>64}::sui::SUI>", "2", "2").parse().unwrap();
+        assert_eq!(short, full);
+        assert_eq!(short.address(), Some(AccountAddress::TWO));
+        assert_eq!(short.to_string(), format!("0x{:0>64}::coin::Coin<0x{:0>64}::sui::SUI>", "2", "2"));
+    }
+
+    #[test]
+    fn test_primitive_and_vector_types() {
+        let on_chain = format!("vector<{:0>64}::sui::SUI>", "2");
+        let bytes = bcs::to_bytes(&on_chain).unwrap();
+
+        let vector: TypeName = bcs::from_bytes(&bytes).unwrap();
+        assert_eq!(vector.struct_tag(), None);
+        assert_eq!(bcs::to_bytes(&vector).unwrap(), bytes);
+
+        let primitive: TypeName = bcs::from_bytes(&bcs::to_bytes("u64").unwrap()).unwrap();
+        assert_eq!(primitive.type_tag(), &TypeTag::U64);
+        assert_eq!(primitive.to_on_chain_string(), "u64");
     }
 }
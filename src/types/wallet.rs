@@ -0,0 +1,138 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use sui_types::base_types::SuiAddress;
+
+use crate::types::bag::Balancez;
+
+/// Represents all balances held by a single wallet address
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletBalances {
+    /// The wallet address
+    pub address: SuiAddress,
+    /// The primary SuiNS name of the address, if any
+    pub primary_name: Option<String>,
+    /// List of balance entries in the wallet
+    pub balances: Vec<Balancez>,
+}
+
+/// Represents a balance snapshot of many wallet addresses
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiWalletBalances {
+    /// Balances of each requested address, in request order
+    pub wallets: Vec<WalletBalances>,
+    /// Balances summed across all requested addresses, per coin type
+    pub totals: Vec<Balancez>,
+}
+
+/// Represents the balances of a wallet for a requested set of coin types
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoinTypeBalances {
+    /// The wallet address
+    pub address: SuiAddress,
+    /// Balance entries for requested coin types the wallet holds
+    pub balances: Vec<Balancez>,
+    /// Requested coin types the wallet holds none of
+    pub zero_balance: Vec<String>,
+    /// Raw balances of requested coin types that have no coin metadata
+    pub unknown_metadata: HashMap<String, u64>,
+}
+
+/// Options controlling how coin fragmentation is detected
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FragmentationOptions {
+    /// A coin is tiny when its balance is below this share of the total balance of its type
+    pub dust_ratio: f64,
+    /// Absolute raw thresholds per coin type, overriding `dust_ratio` for those types
+    pub dust_thresholds: HashMap<String, u64>,
+    /// Minimum number of tiny coins for a coin type to be flagged as fragmented
+    pub min_dust_coins: usize,
+}
+
+impl Default for FragmentationOptions {
+    fn default() -> Self {
+        Self {
+            dust_ratio: 0.01,
+            dust_thresholds: HashMap::new(),
+            min_dust_coins: 10,
+        }
+    }
+}
+
+/// Represents the fragmentation of a single coin type in a wallet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoinFragmentation {
+    /// The coin type (e.g., "0x2::sui::SUI")
+    pub coin_type: String,
+    /// Number of coin objects of this type
+    pub coin_count: usize,
+    /// Sum of all coin balances of this type
+    pub total_balance: u128,
+    /// Number of coin objects below the dust threshold
+    pub dust_count: usize,
+    /// Whether the coin type has enough tiny coins to be worth consolidating
+    pub is_fragmented: bool,
+    /// Number of merge transactions needed to consolidate into a single coin
+    pub merge_transactions: usize,
+    /// Estimated storage rebate in MIST returned by consolidating into a single coin
+    pub recoverable_storage_rebate: u64,
+}
+
+/// Represents the coin fragmentation of a wallet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FragmentationReport {
+    /// The wallet address
+    pub address: SuiAddress,
+    /// Fragmentation per coin type, ordered by coin type
+    pub coin_types: Vec<CoinFragmentation>,
+    /// Number of merge transactions needed to consolidate all coin types
+    pub total_merge_transactions: usize,
+    /// Estimated storage rebate in MIST returned by consolidating all coin types
+    pub total_recoverable_storage_rebate: u64,
+}
+
+/// A point in the past at which balances are reconstructed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BalancePoint {
+    /// Balances right after the given checkpoint sequence number
+    Checkpoint(u64),
+    /// Balances at the given Unix timestamp in milliseconds
+    Timestamp(u64),
+}
+
+impl BalancePoint {
+    /// Check whether this point precedes a transaction, i.e. the transaction happened after it
+    ///
+    /// Transactions not yet included in a checkpoint are always after the point.
+    ///
+    /// # Arguments
+    /// * `checkpoint` - The checkpoint containing the transaction
+    /// * `timestamp_ms` - The timestamp of that checkpoint in milliseconds
+    ///
+    /// # Returns
+    /// * `bool` - Whether the transaction happened after this point
+    pub fn precedes(&self, checkpoint: Option<u64>, timestamp_ms: Option<u64>) -> bool {
+        match self {
+            BalancePoint::Checkpoint(point) => checkpoint.map_or(true, |checkpoint| checkpoint > *point),
+            BalancePoint::Timestamp(point) => timestamp_ms.map_or(true, |timestamp_ms| timestamp_ms > *point),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_balance_point_precedes() {
+        let checkpoint = BalancePoint::Checkpoint(100);
+        assert!(checkpoint.precedes(Some(101), Some(0)));
+        assert!(!checkpoint.precedes(Some(100), Some(u64::MAX)));
+        assert!(!checkpoint.precedes(Some(99), None));
+        assert!(checkpoint.precedes(None, None));
+
+        let timestamp = BalancePoint::Timestamp(1_000);
+        assert!(timestamp.precedes(Some(0), Some(1_001)));
+        assert!(!timestamp.precedes(Some(u64::MAX), Some(1_000)));
+        assert!(timestamp.precedes(None, None));
+    }
+}
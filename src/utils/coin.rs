@@ -1,11 +1,25 @@
+use std::str::FromStr;
+use sui_types::TypeTag;
+
+/// Coin type of the native SUI coin, as reported by the balance RPCs
+pub const SUI_COIN_TYPE: &str = "0x2::sui::SUI";
+
+/// Number of coins merged into the primary coin by a single transaction
+pub const MAX_MERGE_COINS_PER_TRANSACTION: usize = 500;
+
+/// Share of the storage rebate returned when an object is deleted, in basis points
+pub const STORAGE_REBATE_RATE_BPS: u64 = 9900;
+
 /**
- * Formats a coin address by padding the hexadecimal part to 64 characters.
+ * Formats a coin address by padding every hexadecimal address to 64 characters.
  * 
- * If the input string contains "::", it splits the string at that position,
- * takes the part before "::" as a hexadecimal address, pads it to 64 characters,
- * and then rejoins it with the part after "::".
+ * Addresses are recognized at the start of the string and of every type
+ * parameter, when followed by "::", with or without a "0x" prefix. Each is
+ * padded to 64 characters and prefixed with "0x", so
+ * "0x2::coin::Coin<0x2::sui::SUI>" becomes
+ * "0x00..02::coin::Coin<0x00..02::sui::SUI>".
  * 
- * If the input doesn't contain "::", it returns the original string unchanged.
+ * If the input string doesn't contain "::", it returns the original string unchanged.
  * 
  * @params - The coin address string to format
  * @return The formatted coin address
@@ -15,14 +29,66 @@ pub fn format_coin_address(s: &str) -> String {
         return s.to_string();
     }
 
-    if let Some(pos) = s.find("::") {
-        let (hex_part, rest) = s.split_at(pos);
-        
-        let hex_str = hex_part.strip_prefix("0x").unwrap_or(hex_part);
-        let padded_hex_str = format!("{:0>64}", hex_str);
-        
-        format!("0x{}{}", padded_hex_str, rest)
-    } else {
-        s.to_string()
+    let mut formatted = String::with_capacity(s.len() + 64);
+    let mut rest = s;
+    let mut token_start = true;
+    while let Some(c) = rest.chars().next() {
+        if token_start {
+            if let Some(end) = rest.find("::") {
+                let candidate = &rest[..end];
+                let hex_str = candidate.strip_prefix("0x").unwrap_or(candidate);
+                if !hex_str.is_empty() && hex_str.len() <= 64 && hex_str.chars().all(|c| c.is_ascii_hexdigit()) {
+                    formatted.push_str(&format!("0x{:0>64}", hex_str));
+                    rest = &rest[end..];
+                    token_start = false;
+                    continue;
+                }
+            }
+        }
+
+        token_start = matches!(c, '<' | ',' | ' ');
+        formatted.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    formatted
+}
+
+/// Normalizes a coin type string so that equivalent spellings compare equal.
+///
+/// Addresses are expanded to their full 64 character form, so
+/// "0x2::sui::SUI" and "0x0000...0002::sui::SUI" normalize to the same string.
+/// Strings that cannot be parsed as a type fall back to `format_coin_address`.
+pub fn normalize_coin_type(s: &str) -> String {
+    match TypeTag::from_str(s) {
+        Ok(type_tag) => type_tag.to_canonical_string(true),
+        Err(_) => format_coin_address(s),
+    }
+}
+
+/// Computes how many merge transactions are needed to consolidate coins into one.
+///
+/// Each transaction merges up to `MAX_MERGE_COINS_PER_TRANSACTION` coins into the primary coin.
+pub fn merge_transactions_needed(coin_count: usize) -> usize {
+    coin_count.saturating_sub(1).div_ceil(MAX_MERGE_COINS_PER_TRANSACTION)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_coin_address_pads_nested_addresses() {
+        let sui = format!("0x{:0>64}::sui::SUI", "2");
+        assert_eq!(format_coin_address("0x2::sui::SUI"), sui);
+        assert_eq!(format_coin_address(&sui[2..]), sui);
+        assert_eq!(
+            format_coin_address("0x2::coin::Coin<0x2::sui::SUI>"),
+            format!("0x{:0>64}::coin::Coin<{}>", "2", sui)
+        );
+        assert_eq!(
+            format_coin_address("0x2::dynamic_field::Field<bool, 0x2::sui::SUI>"),
+            format!("0x{:0>64}::dynamic_field::Field<bool, {}>", "2", sui)
+        );
+        assert_eq!(format_coin_address("u64"), "u64");
     }
 }
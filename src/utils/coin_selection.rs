@@ -0,0 +1,218 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use sui_json_rpc_types::Coin;
+
+/// Maximum number of subsets explored when looking for an exact match
+const EXACT_MATCH_SEARCH_LIMIT: usize = 100_000;
+
+/// Strategy used to pick coins covering a target amount
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CoinSelectionStrategy {
+    /// Take the largest coins first until the target is covered
+    LargestFirst,
+    /// Use as few coins as possible, preferring the smallest coin that completes the target
+    FewestObjects,
+    /// Find coins summing exactly to the target, falling back to `FewestObjects`
+    ExactMatch,
+}
+
+/// Represents the coins picked to cover a target amount
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoinSelection {
+    /// The selected coins
+    pub coins: Vec<Coin>,
+    /// The amount the coins were selected for
+    pub target: u64,
+    /// The sum of the selected coin balances
+    pub total: u64,
+    /// The amount left over after paying the target (`total - target`)
+    pub change: u64,
+    /// Whether the selected coins should be merged before use
+    pub merge_recommended: bool,
+}
+
+/// Select coins covering a target amount
+///
+/// # Arguments
+/// * `coins` - The coins available for selection
+/// * `target` - The amount to cover
+/// * `strategy` - The strategy used to pick coins
+///
+/// # Returns
+/// * `Result<CoinSelection>` - The selected coins or an error if the coins cannot cover the target
+pub fn select_coins(coins: &[Coin], target: u64, strategy: CoinSelectionStrategy) -> Result<CoinSelection> {
+    let available: u128 = coins.iter().map(|coin| coin.balance as u128).sum();
+    if available < target as u128 {
+        return Err(anyhow!("Insufficient balance, required: {}, available: {}", target, available));
+    }
+
+    let mut sorted: Vec<&Coin> = coins.iter().collect();
+    sorted.sort_by(|a, b| b.balance.cmp(&a.balance));
+
+    let selected = match strategy {
+        CoinSelectionStrategy::LargestFirst => largest_first(&sorted, target),
+        CoinSelectionStrategy::FewestObjects => fewest_objects(&sorted, target),
+        CoinSelectionStrategy::ExactMatch => exact_match(&sorted, target).unwrap_or_else(|| fewest_objects(&sorted, target)),
+    };
+
+    let total = selected.iter().map(|coin| coin.balance).sum();
+    Ok(CoinSelection {
+        merge_recommended: selected.len() > 1,
+        coins: selected.into_iter().cloned().collect(),
+        target,
+        total,
+        change: total - target,
+    })
+}
+
+/// Take coins from the largest down until the target is covered
+fn largest_first<'a>(sorted: &[&'a Coin], target: u64) -> Vec<&'a Coin> {
+    let mut selected = vec![];
+    let mut total: u64 = 0;
+    for coin in sorted {
+        if total >= target {
+            break;
+        }
+        total += coin.balance;
+        selected.push(*coin);
+    }
+    selected
+}
+
+/// Use the minimum number of coins, completing the target with the smallest sufficient coin
+fn fewest_objects<'a>(sorted: &[&'a Coin], target: u64) -> Vec<&'a Coin> {
+    // Largest-first yields the minimum coin count; only its last pick can be improved
+    let mut selected = largest_first(sorted, target);
+    let Some(last) = selected.pop() else {
+        return selected;
+    };
+
+    let covered: u64 = selected.iter().map(|coin| coin.balance).sum();
+    let remaining = target.saturating_sub(covered);
+    let smallest_sufficient = sorted[selected.len()..]
+        .iter()
+        .rev()
+        .find(|coin| coin.balance >= remaining)
+        .copied()
+        .unwrap_or(last);
+    selected.push(smallest_sufficient);
+    selected
+}
+
+/// Find coins summing exactly to the target with a bounded depth-first search
+fn exact_match<'a>(sorted: &[&'a Coin], target: u64) -> Option<Vec<&'a Coin>> {
+    if let Some(coin) = sorted.iter().find(|coin| coin.balance == target) {
+        return Some(vec![*coin]);
+    }
+
+    // suffix_sums[i] is the sum of all coins from index i, used to prune branches
+    let mut suffix_sums = vec![0u128; sorted.len() + 1];
+    for i in (0..sorted.len()).rev() {
+        suffix_sums[i] = suffix_sums[i + 1] + sorted[i].balance as u128;
+    }
+
+    let mut path = vec![];
+    let mut budget = EXACT_MATCH_SEARCH_LIMIT;
+    if search_exact(sorted, &suffix_sums, 0, target, &mut path, &mut budget) {
+        Some(path.into_iter().map(|i| sorted[i]).collect())
+    } else {
+        None
+    }
+}
+
+fn search_exact(
+    sorted: &[&Coin],
+    suffix_sums: &[u128],
+    start: usize,
+    remaining: u64,
+    path: &mut Vec<usize>,
+    budget: &mut usize,
+) -> bool {
+    if remaining == 0 {
+        return true;
+    }
+    if *budget == 0 || suffix_sums[start] < remaining as u128 {
+        return false;
+    }
+    *budget -= 1;
+
+    for i in start..sorted.len() {
+        let balance = sorted[i].balance;
+        if balance > remaining {
+            continue;
+        }
+        // Coins of equal balance lead to the same subsets, so only try the first one
+        if i > start && sorted[i - 1].balance == balance {
+            continue;
+        }
+
+        path.push(i);
+        if search_exact(sorted, suffix_sums, i + 1, remaining - balance, path, budget) {
+            return true;
+        }
+        path.pop();
+
+        if *budget == 0 {
+            return false;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_types::base_types::{ObjectDigest, ObjectID, SequenceNumber, TransactionDigest};
+
+    fn coins(balances: &[u64]) -> Vec<Coin> {
+        balances
+            .iter()
+            .map(|balance| Coin {
+                coin_type: "0x2::sui::SUI".to_string(),
+                coin_object_id: ObjectID::random(),
+                version: SequenceNumber::new(),
+                digest: ObjectDigest::random(),
+                balance: *balance,
+                previous_transaction: TransactionDigest::random(),
+            })
+            .collect()
+    }
+
+    fn balances(selection: &CoinSelection) -> Vec<u64> {
+        selection.coins.iter().map(|coin| coin.balance).collect()
+    }
+
+    #[test]
+    fn test_largest_first() {
+        let selection = select_coins(&coins(&[10, 50, 30, 5]), 70, CoinSelectionStrategy::LargestFirst).unwrap();
+        assert_eq!(balances(&selection), vec![50, 30]);
+        assert_eq!(selection.change, 10);
+        assert!(selection.merge_recommended);
+    }
+
+    #[test]
+    fn test_fewest_objects_prefers_smallest_sufficient_coin() {
+        let selection = select_coins(&coins(&[100, 40, 25, 5]), 20, CoinSelectionStrategy::FewestObjects).unwrap();
+        assert_eq!(balances(&selection), vec![25]);
+        assert!(!selection.merge_recommended);
+    }
+
+    #[test]
+    fn test_exact_match() {
+        let selection = select_coins(&coins(&[60, 45, 30, 7, 3]), 48, CoinSelectionStrategy::ExactMatch).unwrap();
+        assert_eq!(selection.total, 48);
+        assert_eq!(selection.change, 0);
+    }
+
+    #[test]
+    fn test_exact_match_falls_back() {
+        let selection = select_coins(&coins(&[60, 45]), 50, CoinSelectionStrategy::ExactMatch).unwrap();
+        assert_eq!(balances(&selection), vec![60]);
+        assert_eq!(selection.change, 10);
+    }
+
+    #[test]
+    fn test_insufficient_balance() {
+        assert!(select_coins(&coins(&[10, 20]), 31, CoinSelectionStrategy::LargestFirst).is_err());
+    }
+}
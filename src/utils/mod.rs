@@ -1,2 +1,3 @@
 pub mod coin;
+pub mod coin_selection;
 pub mod object;
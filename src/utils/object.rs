@@ -1,6 +1,9 @@
 use anyhow::{anyhow, Result};
-use sui_json_rpc_types::{SuiObjectData, SuiObjectResponse};
-use sui_types::base_types::ObjectID;
+use serde::de::DeserializeOwned;
+use sui_json_rpc_types::{SuiData, SuiObjectData, SuiObjectResponse};
+use sui_types::base_types::{ObjectID, ObjectType};
+
+use crate::types::inventory::{InventoryItem, ObjectDisplay};
 
 /// Extract object data from a SuiObjectResponse
 ///
@@ -22,4 +25,50 @@ pub fn extract_object_data(response: SuiObjectResponse) -> Result<SuiObjectData>
 /// * `Result<ObjectID>` - The parsed ObjectID or an error
 pub fn parse_object_id(id_str: &str) -> Result<ObjectID> {
     id_str.parse::<ObjectID>().map_err(|e| anyhow!("Failed to parse object ID: {}", e))
-} 
+}
+
+/// Decode the BCS contents of a Move object
+///
+/// The object data must have been fetched with its BCS contents.
+///
+/// # Arguments
+/// * `data` - The object data to decode
+///
+/// # Returns
+/// * `Result<T>` - The decoded Move object or an error
+pub fn parse_move_object_bcs<T: DeserializeOwned>(data: &SuiObjectData) -> Result<T> {
+    let move_object = data.bcs
+        .as_ref()
+        .ok_or(anyhow!("object data bcs is none"))?
+        .try_as_move()
+        .ok_or(anyhow!("object data bcs is not move"))?;
+    Ok(bcs::from_bytes(&move_object.bcs_bytes)?)
+}
+
+/// Convert object data to an inventory item
+///
+/// The object data must have been fetched with its type, and optionally its display.
+///
+/// # Arguments
+/// * `data` - The object data to convert
+///
+/// # Returns
+/// * `Option<InventoryItem>` - The inventory item, or `None` for packages, coins and untyped data
+pub fn to_inventory_item(data: SuiObjectData) -> Option<InventoryItem> {
+    let object_type = data.type_?;
+    match &object_type {
+        ObjectType::Struct(move_type) if !move_type.is_coin() => {}
+        _ => return None,
+    }
+
+    let display = data.display
+        .and_then(|display| display.data)
+        .map(ObjectDisplay::from_fields);
+
+    Some(InventoryItem {
+        object_id: data.object_id,
+        version: data.version.value(),
+        object_type: object_type.to_string(),
+        display,
+    })
+}